@@ -0,0 +1,34 @@
+/* C interface to simple_rng, built with `make ffi` (requires `ffi` feature).
+ *
+ * A handle seeded with `seed` produces exactly the sequence of the Rust
+ * `RNG::new(seed)`. Handles are not thread-safe; give each thread its own.
+ */
+
+#ifndef SIMPLE_RNG_H
+#define SIMPLE_RNG_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct SimpleRng SimpleRng;
+
+/* Create a generator seeded with `seed`, to be released with simplerng_free */
+SimpleRng *simplerng_new(uint64_t seed);
+
+/* Next random 64-bit value */
+uint64_t simplerng_next(SimpleRng *rng);
+
+/* Random integer in [min, max] (inclusive), or `min` if `max < min` */
+uint64_t simplerng_range(SimpleRng *rng, uint64_t min, uint64_t max);
+
+/* Release a generator, doing nothing for NULL */
+void simplerng_free(SimpleRng *rng);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* SIMPLE_RNG_H */
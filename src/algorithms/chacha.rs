@@ -0,0 +1,325 @@
+use super::expand_seed;
+use super::portable::impl_portable;
+use super::sha256::Sha256;
+#[cfg(feature = "zeroize")]
+use super::wipe;
+
+// "expand 32-byte k"
+const CONSTANTS: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+
+/// ChaCha stream cipher used as a cryptographically secure generator (requires
+/// `crypto` feature)
+///
+/// Uses Bernstein's original layout: a 256-bit key, a 64-bit block counter and a
+/// 64-bit stream id. Each block yields eight `u64` outputs.
+///
+/// Seeding from a `u64` only provides 64 bits of key material; seed from a
+/// high-entropy source when unpredictability matters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChaCha<const ROUNDS: usize> {
+    key: [u32; 8],
+    counter: u64,
+    stream: u64,
+    block: [u64; 8],
+    index: usize,
+}
+
+/// ChaCha with 20 rounds, the standard cipher strength
+pub type ChaCha20 = ChaCha<20>;
+/// ChaCha with 12 rounds
+pub type ChaCha12 = ChaCha<12>;
+/// ChaCha with 8 rounds, the fastest variant
+pub type ChaCha8 = ChaCha<8>;
+
+impl<const ROUNDS: usize> ChaCha<ROUNDS> {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self::with_stream(seed, 0)
+    }
+
+    pub(crate) fn with_stream(seed: u64, stream: u64) -> Self {
+        let words: [u64; 4] = expand_seed(seed);
+        let mut key = [0u32; 8];
+        for (i, word) in words.iter().enumerate() {
+            key[2 * i] = *word as u32;
+            key[2 * i + 1] = (*word >> 32) as u32;
+        }
+        Self {
+            key,
+            counter: 0,
+            stream,
+            block: [0; 8],
+            index: 8,
+        }
+    }
+
+    /// Use the seed bytes as the 256-bit key, little-endian, on stream 0
+    pub(crate) fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        let (words, _) = seed.as_chunks::<4>();
+        Self {
+            key: core::array::from_fn(|i| u32::from_le_bytes(words[i])),
+            counter: 0,
+            stream: 0,
+            block: [0; 8],
+            index: 8,
+        }
+    }
+
+    pub(crate) fn seed_word(&self) -> u64 {
+        (self.key[1] as u64) << 32 | self.key[0] as u64
+    }
+
+    /// Replace the key with SHA-256 of the current key, position and `input`,
+    /// keeping the stream and starting a fresh block, and return `true`
+    pub(crate) fn rekey(&mut self, input: &[u8]) -> bool {
+        let mut hash = Sha256::new();
+        for word in self.key {
+            hash.update(&word.to_le_bytes());
+        }
+        hash.update(&self.stream.to_le_bytes());
+        hash.update(&self.counter.to_le_bytes());
+        hash.update(&(self.index as u64).to_le_bytes());
+        hash.update(input);
+        let stream = self.stream;
+        *self = Self::from_seed_bytes(hash.finalize());
+        self.stream = stream;
+        true
+    }
+
+    /// Rekey as the generator for worker `worker_index`, putting it on stream
+    /// `worker_index`
+    pub(crate) fn rekey_worker(&mut self, worker_index: u64) -> bool {
+        self.rekey(&worker_index.to_le_bytes());
+        self.stream = worker_index;
+        true
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        if self.index >= 8 {
+            self.refill();
+        }
+        let value = self.block[self.index];
+        self.index += 1;
+        value
+    }
+
+    /// Fill `dest` with consecutive outputs, writing whole blocks in place
+    pub(crate) fn fill_u64(&mut self, dest: &mut [u64]) {
+        let buffered = (8 - self.index).min(dest.len());
+        let (head, rest) = dest.split_at_mut(buffered);
+        head.copy_from_slice(&self.block[self.index..self.index + buffered]);
+        self.index += buffered;
+
+        let (blocks, tail) = rest.as_chunks_mut::<8>();
+        for block in blocks {
+            *block = self.next_block();
+        }
+        if !tail.is_empty() {
+            self.refill();
+            tail.copy_from_slice(&self.block[..tail.len()]);
+            self.index = tail.len();
+        }
+    }
+
+    fn refill(&mut self) {
+        self.block = self.next_block();
+        self.index = 0;
+    }
+
+    // Generate the keystream block for the current counter, then bump the counter
+    fn next_block(&mut self) -> [u64; 8] {
+        let mut input = [0u32; 16];
+        input[..4].copy_from_slice(&CONSTANTS);
+        input[4..12].copy_from_slice(&self.key);
+        input[12] = self.counter as u32;
+        input[13] = (self.counter >> 32) as u32;
+        input[14] = self.stream as u32;
+        input[15] = (self.stream >> 32) as u32;
+
+        let output = chacha_block::<ROUNDS>(&input);
+        let mut block = [0u64; 8];
+        for (value, pair) in block.iter_mut().zip(output.chunks_exact(2)) {
+            *value = (pair[1] as u64) << 32 | pair[0] as u64;
+        }
+        self.counter = self.counter.wrapping_add(1);
+        block
+    }
+}
+
+// ChaCha block function with ROUNDS rounds
+fn chacha_block<const ROUNDS: usize>(input: &[u32; 16]) -> [u32; 16] {
+    let mut x = *input;
+    for _ in 0..ROUNDS / 2 {
+        quarter_round(&mut x, 0, 4, 8, 12);
+        quarter_round(&mut x, 1, 5, 9, 13);
+        quarter_round(&mut x, 2, 6, 10, 14);
+        quarter_round(&mut x, 3, 7, 11, 15);
+        quarter_round(&mut x, 0, 5, 10, 15);
+        quarter_round(&mut x, 1, 6, 11, 12);
+        quarter_round(&mut x, 2, 7, 8, 13);
+        quarter_round(&mut x, 3, 4, 9, 14);
+    }
+    for (word, initial) in x.iter_mut().zip(input) {
+        *word = word.wrapping_add(*initial);
+    }
+    x
+}
+
+fn quarter_round(x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    x[a] = x[a].wrapping_add(x[b]);
+    x[d] = (x[d] ^ x[a]).rotate_left(16);
+    x[c] = x[c].wrapping_add(x[d]);
+    x[b] = (x[b] ^ x[c]).rotate_left(12);
+    x[a] = x[a].wrapping_add(x[b]);
+    x[d] = (x[d] ^ x[a]).rotate_left(8);
+    x[c] = x[c].wrapping_add(x[d]);
+    x[b] = (x[b] ^ x[c]).rotate_left(7);
+}
+
+#[cfg(feature = "zeroize")]
+impl<const ROUNDS: usize> ChaCha<ROUNDS> {
+    /// Overwrite the key, counters and buffered outputs with zeros (requires
+    /// `zeroize` feature)
+    ///
+    /// Dropping the generator does this automatically. The writes cannot be
+    /// optimised away, but copies left behind by `clone` or by moving the
+    /// value are not touched.
+    pub fn zeroize(&mut self) {
+        wipe(&mut self.key, [0; 8]);
+        wipe(&mut self.counter, 0);
+        wipe(&mut self.stream, 0);
+        wipe(&mut self.block, [0; 8]);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<const ROUNDS: usize> Drop for ChaCha<ROUNDS> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl_portable!(ChaCha<const ROUNDS> { key, counter, stream, block, index } if |this| this.index <= 8);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zero_key<const ROUNDS: usize>() -> ChaCha<ROUNDS> {
+        ChaCha {
+            key: [0; 8],
+            counter: 0,
+            stream: 0,
+            block: [0; 8],
+            index: 8,
+        }
+    }
+
+    #[test]
+    /// The block function matches the RFC 8439 section 2.3.2 test vector
+    fn test_rfc8439_block() {
+        let mut input = [0u32; 16];
+        input[..4].copy_from_slice(&CONSTANTS);
+        for (i, word) in input[4..12].iter_mut().enumerate() {
+            let byte = 4 * i as u32;
+            *word =
+                u32::from_le_bytes([byte as u8, byte as u8 + 1, byte as u8 + 2, byte as u8 + 3]);
+        }
+        input[12] = 1;
+        input[13] = 0x09000000;
+        input[14] = 0x4a000000;
+        let expected = [
+            0xe4e7f110, 0x15593bd1, 0x1fdd0f50, 0xc47120a3, 0xc7f4d1c7, 0x0368c033, 0x9aaa2204,
+            0x4e6cd4c3, 0x466482d2, 0x09aa9f07, 0x05d7c214, 0xa2028bd9, 0xd19c12b5, 0xb94e16de,
+            0xe883d0cb, 0x4e3c50a2,
+        ];
+        assert_eq!(chacha_block::<20>(&input), expected);
+    }
+
+    #[test]
+    /// Keystream for the all-zero key matches the published values
+    fn test_zero_key_keystream() {
+        let mut chacha20 = zero_key::<20>();
+        assert_eq!(chacha20.next_u64(), 0x903df1a0ade0b876);
+        assert_eq!(chacha20.next_u64(), 0x28bd8653e56a5d40);
+
+        let mut chacha12 = zero_key::<12>();
+        assert_eq!(chacha12.next_u64(), 0x53f955076a9af49b);
+        assert_eq!(chacha12.next_u64(), 0xd583265f12ce1f81);
+
+        let mut chacha8 = zero_key::<8>();
+        assert_eq!(chacha8.next_u64(), 0xd6405f892fef003e);
+        assert_eq!(chacha8.next_u64(), 0xa1a5091fe8b85b7f);
+    }
+
+    #[test]
+    /// Seed bytes become the key, so the zero seed gives the zero-key keystream
+    fn test_from_seed_bytes() {
+        assert_eq!(ChaCha20::from_seed_bytes([0; 32]), zero_key::<20>());
+        let mut key = [0u8; 32];
+        key[4] = 1;
+        assert_eq!(ChaCha8::from_seed_bytes(key).key[1], 1);
+    }
+
+    #[test]
+    /// Workers are keyed by SHA-256 of the key, position and index, each on its own stream
+    fn test_rekey_worker() {
+        let mut parent = ChaCha20::new(5);
+        parent.next_u64();
+        let mut hash = Sha256::new();
+        for word in parent.key {
+            hash.update(&word.to_le_bytes());
+        }
+        hash.update(&parent.stream.to_le_bytes());
+        hash.update(&parent.counter.to_le_bytes());
+        hash.update(&(parent.index as u64).to_le_bytes());
+        hash.update(&7u64.to_le_bytes());
+        let mut worker = parent.clone();
+        worker.rekey_worker(7);
+        let expected = ChaCha20::from_seed_bytes(hash.finalize());
+        assert_eq!((worker.key, worker.stream), (expected.key, 7));
+
+        let mut later = parent.clone();
+        later.next_u64();
+        later.rekey_worker(7);
+        assert_ne!(later.key, worker.key);
+    }
+
+    #[test]
+    /// Consecutive blocks use consecutive counters
+    fn test_block_counter() {
+        let mut rng = zero_key::<20>();
+        for _ in 0..8 {
+            rng.next_u64();
+        }
+        assert_eq!(rng.counter, 1);
+        rng.next_u64();
+        assert_eq!(rng.counter, 2);
+    }
+
+    #[test]
+    /// Filling in place gives the same outputs as drawing them one by one
+    fn test_fill_u64() {
+        let mut single = ChaCha20::new(5);
+        let mut filled = ChaCha20::new(5);
+        single.next_u64();
+        filled.next_u64();
+        let mut out = [0u64; 29];
+        filled.fill_u64(&mut out);
+        for value in out {
+            assert_eq!(value, single.next_u64());
+        }
+        assert_eq!(filled.next_u64(), single.next_u64());
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    /// zeroize clears the key and the buffered outputs
+    fn test_zeroize() {
+        let mut chacha = ChaCha20::new(99);
+        chacha.next_u64();
+        chacha.zeroize();
+        assert_eq!(chacha.key, [0; 8]);
+        assert_eq!(chacha.block, [0; 8]);
+        assert_eq!((chacha.counter, chacha.stream), (0, 0));
+    }
+}
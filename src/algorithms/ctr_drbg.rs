@@ -0,0 +1,251 @@
+use super::aes::Aes256;
+use super::portable::impl_portable;
+#[cfg(feature = "zeroize")]
+use super::wipe;
+
+const KEY_LEN: usize = 32;
+const BLOCK_LEN: usize = 16;
+const SEED_LEN: usize = KEY_LEN + BLOCK_LEN;
+
+/// Requests allowed between reseeds, the SP 800-90A maximum
+const RESEED_INTERVAL: u64 = 1 << 48;
+
+/// CTR_DRBG from NIST SP 800-90A using AES-256 and the derivation function
+/// (requires `drbg` feature)
+///
+/// Every refill of the output buffer is one Generate request of 64 bytes without
+/// additional input. Instantiating from a `u64` seed is provided for the common
+/// `RNG` constructors but supplies far less entropy than the standard requires.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CtrDrbg {
+    cipher: Aes256,
+    v: [u8; BLOCK_LEN],
+    reseed_counter: u64,
+    block: [u64; 8],
+    index: usize,
+}
+
+impl CtrDrbg {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self::instantiate(&seed.to_le_bytes(), &[], &[])
+    }
+
+    pub(crate) fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        Self::instantiate(&seed, &[], &[])
+    }
+
+    /// CTR_DRBG_Instantiate_algorithm with a derivation function
+    pub(crate) fn instantiate(entropy_input: &[u8], nonce: &[u8], personalization: &[u8]) -> Self {
+        let mut drbg = Self {
+            cipher: Aes256::new(&[0; KEY_LEN]),
+            v: [0; BLOCK_LEN],
+            reseed_counter: 1,
+            block: [0; 8],
+            index: 8,
+        };
+        drbg.update(&derive(&[entropy_input, nonce, personalization]));
+        drbg
+    }
+
+    /// CTR_DRBG_Reseed_algorithm with a derivation function
+    pub(crate) fn reseed(&mut self, entropy_input: &[u8], additional_input: &[u8]) {
+        self.update(&derive(&[entropy_input, additional_input]));
+        self.reseed_counter = 1;
+    }
+
+    /// CTR_DRBG_Generate_algorithm with a derivation function
+    pub(crate) fn generate(&mut self, output: &mut [u8], additional_input: &[u8]) {
+        if self.reseed_counter > RESEED_INTERVAL {
+            panic!("DRBG reseed required")
+        }
+        let additional = if additional_input.is_empty() {
+            [0; SEED_LEN]
+        } else {
+            let additional = derive(&[additional_input]);
+            self.update(&additional);
+            additional
+        };
+        for chunk in output.chunks_mut(BLOCK_LEN) {
+            let block = self.next_block();
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+        self.update(&additional);
+        self.reseed_counter += 1;
+    }
+
+    pub(crate) fn seed_word(&self) -> u64 {
+        u64::from_le_bytes(self.v[..8].try_into().unwrap())
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        if self.index >= 8 {
+            let mut bytes = [0u8; 64];
+            self.generate(&mut bytes, &[]);
+            for (value, chunk) in self.block.iter_mut().zip(bytes.chunks_exact(8)) {
+                *value = u64::from_le_bytes(chunk.try_into().unwrap());
+            }
+            self.index = 0;
+        }
+        let value = self.block[self.index];
+        self.index += 1;
+        value
+    }
+
+    // Increment V and encrypt it
+    fn next_block(&mut self) -> [u8; BLOCK_LEN] {
+        self.v = (u128::from_be_bytes(self.v).wrapping_add(1)).to_be_bytes();
+        let mut block = self.v;
+        self.cipher.encrypt_block(&mut block);
+        block
+    }
+
+    // CTR_DRBG_Update
+    fn update(&mut self, provided_data: &[u8; SEED_LEN]) {
+        let mut temp = [0u8; SEED_LEN];
+        for chunk in temp.chunks_exact_mut(BLOCK_LEN) {
+            chunk.copy_from_slice(&self.next_block());
+        }
+        for (byte, provided) in temp.iter_mut().zip(provided_data) {
+            *byte ^= provided;
+        }
+        self.cipher = Aes256::new(temp[..KEY_LEN].try_into().unwrap());
+        self.v.copy_from_slice(&temp[KEY_LEN..]);
+    }
+}
+
+// Block_Cipher_df over the concatenation of `inputs`, returning seedlen bytes
+fn derive(inputs: &[&[u8]]) -> [u8; SEED_LEN] {
+    let length: usize = inputs.iter().map(|input| input.len()).sum();
+    let mut df_key = [0u8; KEY_LEN];
+    for (i, byte) in df_key.iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+    let df_cipher = Aes256::new(&df_key);
+
+    let mut temp = [0u8; SEED_LEN];
+    for (i, chunk) in temp.chunks_exact_mut(BLOCK_LEN).enumerate() {
+        let mut iv = [0u8; BLOCK_LEN];
+        iv[..4].copy_from_slice(&(i as u32).to_be_bytes());
+
+        let mut bcc = Bcc::new(&df_cipher);
+        bcc.absorb(&iv);
+        bcc.absorb(&(length as u32).to_be_bytes());
+        bcc.absorb(&(SEED_LEN as u32).to_be_bytes());
+        for input in inputs {
+            bcc.absorb(input);
+        }
+        bcc.absorb(&[0x80]);
+        chunk.copy_from_slice(&bcc.finish());
+    }
+
+    let cipher = Aes256::new(temp[..KEY_LEN].try_into().unwrap());
+    let mut x: [u8; BLOCK_LEN] = temp[KEY_LEN..].try_into().unwrap();
+    let mut output = [0u8; SEED_LEN];
+    for chunk in output.chunks_exact_mut(BLOCK_LEN) {
+        cipher.encrypt_block(&mut x);
+        chunk.copy_from_slice(&x);
+    }
+    output
+}
+
+// Streaming BCC (CBC-MAC) over zero-padded input
+struct Bcc<'a> {
+    cipher: &'a Aes256,
+    chaining: [u8; BLOCK_LEN],
+    filled: usize,
+}
+
+impl<'a> Bcc<'a> {
+    fn new(cipher: &'a Aes256) -> Self {
+        Self {
+            cipher,
+            chaining: [0; BLOCK_LEN],
+            filled: 0,
+        }
+    }
+
+    fn absorb(&mut self, data: &[u8]) {
+        for byte in data {
+            self.chaining[self.filled] ^= byte;
+            self.filled += 1;
+            if self.filled == BLOCK_LEN {
+                self.cipher.encrypt_block(&mut self.chaining);
+                self.filled = 0;
+            }
+        }
+    }
+
+    fn finish(mut self) -> [u8; BLOCK_LEN] {
+        if self.filled > 0 {
+            self.cipher.encrypt_block(&mut self.chaining);
+        }
+        self.chaining
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl CtrDrbg {
+    /// Overwrite V and the buffered outputs with zeros (requires `zeroize` feature)
+    ///
+    /// Dropping does this automatically, and the AES round keys are wiped when
+    /// the DRBG drops.
+    pub fn zeroize(&mut self) {
+        wipe(&mut self.v, [0; BLOCK_LEN]);
+        wipe(&mut self.block, [0; 8]);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for CtrDrbg {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl_portable!(CtrDrbg { cipher, v, reseed_counter, block, index } if |this| this.index <= 8);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::decode_hex;
+
+    #[test]
+    /// Instantiate, generate and reseed match OpenSSL's CTR-DRBG (AES-256, df)
+    fn test_matches_openssl() {
+        let entropy: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let nonce: [u8; 16] = core::array::from_fn(|i| 0x20 + i as u8);
+        let mut drbg = CtrDrbg::instantiate(&entropy, &nonce, b"pers!");
+        let mut output = [0u8; 64];
+
+        drbg.generate(&mut output, &[]);
+        assert_eq!(
+            output,
+            decode_hex::<64>(
+                "5e108941849eb88708998523bbd39f0dafdffa6733906af39da696e616356179\
+                 bbaba01e789276f559234d7d7bdc87652a2f5d66085a3078efb599e17b8585c1"
+            )
+        );
+
+        drbg.generate(&mut output, &[1, 2, 3, 4]);
+        assert_eq!(
+            output,
+            decode_hex::<64>(
+                "5081a09e15a956b82d1a12dd6d281d2378eb579b1f1bbf379618234123027d8c\
+                 dfcd3b556711f58cf15aa3de3dcdbac126c8c272ea6c7ba8f7311bbecac3eb5a"
+            )
+        );
+
+        // OpenSSL reseeds with the supplied input, then again from its parent source
+        let fresh: [u8; 32] = core::array::from_fn(|i| 0x80 + i as u8);
+        drbg.reseed(&fresh, &[1, 2, 3, 4]);
+        drbg.reseed(&entropy, &[]);
+        drbg.generate(&mut output, &[]);
+        assert_eq!(
+            output,
+            decode_hex::<64>(
+                "9a43e4ee84dcef944b6a8991c9277780a5a878ece2e296c6f1e0e537b4ca4bff\
+                 05d2d5f4e9ba6dedf3ebf1086ef8f57c6bb46ff1da1b3deba38a5d461f495139"
+            )
+        );
+    }
+}
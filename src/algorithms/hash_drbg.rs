@@ -0,0 +1,206 @@
+use super::portable::impl_portable;
+use super::sha256::{DIGEST_LEN, Sha256};
+#[cfg(feature = "zeroize")]
+use super::wipe;
+
+/// seedlen for SHA-256 in bytes (440 bits)
+const SEED_LEN: usize = 55;
+
+/// Requests allowed between reseeds, the SP 800-90A maximum
+const RESEED_INTERVAL: u64 = 1 << 48;
+
+/// Hash_DRBG from NIST SP 800-90A using SHA-256 (requires `drbg` feature)
+///
+/// Every refill of the output buffer is one Generate request of 64 bytes without
+/// additional input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HashDrbg {
+    v: [u8; SEED_LEN],
+    c: [u8; SEED_LEN],
+    reseed_counter: u64,
+    block: [u64; 8],
+    index: usize,
+}
+
+impl HashDrbg {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self::instantiate(&seed.to_le_bytes(), &[], &[])
+    }
+
+    pub(crate) fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        Self::instantiate(&seed, &[], &[])
+    }
+
+    /// Hash_DRBG_Instantiate_algorithm
+    pub(crate) fn instantiate(entropy_input: &[u8], nonce: &[u8], personalization: &[u8]) -> Self {
+        let v = hash_df(&[entropy_input, nonce, personalization]);
+        Self {
+            c: hash_df(&[&[0x00], &v]),
+            v,
+            reseed_counter: 1,
+            block: [0; 8],
+            index: 8,
+        }
+    }
+
+    /// Hash_DRBG_Reseed_algorithm
+    pub(crate) fn reseed(&mut self, entropy_input: &[u8], additional_input: &[u8]) {
+        self.v = hash_df(&[&[0x01], &self.v, entropy_input, additional_input]);
+        self.c = hash_df(&[&[0x00], &self.v]);
+        self.reseed_counter = 1;
+    }
+
+    /// Hash_DRBG_Generate_algorithm
+    pub(crate) fn generate(&mut self, output: &mut [u8], additional_input: &[u8]) {
+        if self.reseed_counter > RESEED_INTERVAL {
+            panic!("DRBG reseed required")
+        }
+        if !additional_input.is_empty() {
+            let w = hash(&[&[0x02], &self.v, additional_input]);
+            add_assign(&mut self.v, &w);
+        }
+
+        // Hashgen
+        let mut data = self.v;
+        for chunk in output.chunks_mut(DIGEST_LEN) {
+            let digest = hash(&[&data]);
+            chunk.copy_from_slice(&digest[..chunk.len()]);
+            add_assign(&mut data, &[1]);
+        }
+
+        let h = hash(&[&[0x03], &self.v]);
+        let c = self.c;
+        add_assign(&mut self.v, &h);
+        add_assign(&mut self.v, &c);
+        add_assign(&mut self.v, &self.reseed_counter.to_be_bytes());
+        self.reseed_counter += 1;
+    }
+
+    pub(crate) fn seed_word(&self) -> u64 {
+        u64::from_le_bytes(self.v[..8].try_into().unwrap())
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        if self.index >= 8 {
+            let mut bytes = [0u8; 64];
+            self.generate(&mut bytes, &[]);
+            for (value, chunk) in self.block.iter_mut().zip(bytes.chunks_exact(8)) {
+                *value = u64::from_le_bytes(chunk.try_into().unwrap());
+            }
+            self.index = 0;
+        }
+        let value = self.block[self.index];
+        self.index += 1;
+        value
+    }
+}
+
+// SHA-256 over the concatenation of `parts`
+fn hash(parts: &[&[u8]]) -> [u8; DIGEST_LEN] {
+    let mut sha = Sha256::new();
+    for part in parts {
+        sha.update(part);
+    }
+    sha.finalize()
+}
+
+// Hash_df over the concatenation of `parts`, returning seedlen bytes
+fn hash_df(parts: &[&[u8]]) -> [u8; SEED_LEN] {
+    let bits = (SEED_LEN as u32 * 8).to_be_bytes();
+    let mut output = [0u8; SEED_LEN];
+    for (counter, chunk) in output.chunks_mut(DIGEST_LEN).enumerate() {
+        let mut sha = Sha256::new();
+        sha.update(&[counter as u8 + 1]);
+        sha.update(&bits);
+        for part in parts {
+            sha.update(part);
+        }
+        chunk.copy_from_slice(&sha.finalize()[..chunk.len()]);
+    }
+    output
+}
+
+// value = (value + addend) mod 2^seedlen, both big-endian
+fn add_assign(value: &mut [u8; SEED_LEN], addend: &[u8]) {
+    let mut carry = 0u16;
+    let mut addend = addend.iter().rev();
+    for byte in value.iter_mut().rev() {
+        let sum = *byte as u16 + *addend.next().unwrap_or(&0) as u16 + carry;
+        *byte = sum as u8;
+        carry = sum >> 8;
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl HashDrbg {
+    /// Overwrite V, C and the buffered outputs with zeros (requires `zeroize`
+    /// feature), as happens on drop
+    pub fn zeroize(&mut self) {
+        wipe(&mut self.v, [0; SEED_LEN]);
+        wipe(&mut self.c, [0; SEED_LEN]);
+        wipe(&mut self.block, [0; 8]);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for HashDrbg {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl_portable!(HashDrbg { v, c, reseed_counter, block, index } if |this| this.index <= 8);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::decode_hex;
+
+    #[test]
+    /// Instantiate, generate and reseed match OpenSSL's HASH-DRBG (SHA-256)
+    fn test_matches_openssl() {
+        let entropy: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let nonce: [u8; 16] = core::array::from_fn(|i| 0x20 + i as u8);
+        let mut drbg = HashDrbg::instantiate(&entropy, &nonce, b"pers!");
+        let mut output = [0u8; 64];
+
+        drbg.generate(&mut output, &[]);
+        assert_eq!(
+            output,
+            decode_hex::<64>(
+                "3629e0f00a5a076cde38d09990dea9e67577afc7708a10ac52d8640e208b52df\
+                 7d866750072b92537e67b71ad078201455e86686721e53d40d356786d5034153"
+            )
+        );
+
+        drbg.generate(&mut output, &[1, 2, 3, 4]);
+        assert_eq!(
+            output,
+            decode_hex::<64>(
+                "44cc1cfb930a909970e6f772ba99dc7f0dd84a5e14ac9d68962bfa238d43200b\
+                 9b0f97e50cf57c59cd117e143bbfc585bd2a3272da15b43f880b6f701adf709d"
+            )
+        );
+
+        // OpenSSL reseeds with the supplied input, then again from its parent source
+        let fresh: [u8; 32] = core::array::from_fn(|i| 0x80 + i as u8);
+        drbg.reseed(&fresh, &[1, 2, 3, 4]);
+        drbg.reseed(&entropy, &[]);
+        drbg.generate(&mut output, &[]);
+        assert_eq!(
+            output,
+            decode_hex::<64>(
+                "a72483fdebcd1c3e669ff3229378919241f7e33109c708d13cc4dca7861ea5e5\
+                 d67a243d4069c1df851c76ba57ac77d91d79a355d2fff95347c43f4ca42855be"
+            )
+        );
+    }
+
+    #[test]
+    /// Big-endian addition wraps modulo 2^440
+    fn test_add_assign_wraps() {
+        let mut value = [0xff; SEED_LEN];
+        add_assign(&mut value, &[1]);
+        assert_eq!(value, [0; SEED_LEN]);
+    }
+}
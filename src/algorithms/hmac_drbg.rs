@@ -0,0 +1,170 @@
+use super::portable::impl_portable;
+use super::sha256::{DIGEST_LEN, hmac_sha256};
+#[cfg(feature = "zeroize")]
+use super::wipe;
+
+/// Requests allowed between reseeds, the SP 800-90A maximum
+const RESEED_INTERVAL: u64 = 1 << 48;
+
+/// HMAC_DRBG from NIST SP 800-90A using HMAC-SHA-256 (requires `drbg` feature)
+///
+/// Every refill of the output buffer is one Generate request of 64 bytes without
+/// additional input. The deterministic construction makes it suitable for RFC 6979
+/// style nonce derivation when instantiated through [`RNG::instantiate_drbg`].
+///
+/// [`RNG::instantiate_drbg`]: crate::RNG::instantiate_drbg
+#[derive(Clone, Debug, PartialEq)]
+pub struct HmacDrbg {
+    key: [u8; DIGEST_LEN],
+    v: [u8; DIGEST_LEN],
+    reseed_counter: u64,
+    block: [u64; 8],
+    index: usize,
+}
+
+impl HmacDrbg {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self::instantiate(&seed.to_le_bytes(), &[], &[])
+    }
+
+    pub(crate) fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        Self::instantiate(&seed, &[], &[])
+    }
+
+    /// HMAC_DRBG_Instantiate_algorithm
+    pub(crate) fn instantiate(entropy_input: &[u8], nonce: &[u8], personalization: &[u8]) -> Self {
+        let mut drbg = Self {
+            key: [0x00; DIGEST_LEN],
+            v: [0x01; DIGEST_LEN],
+            reseed_counter: 1,
+            block: [0; 8],
+            index: 8,
+        };
+        drbg.update(&[entropy_input, nonce, personalization]);
+        drbg
+    }
+
+    /// HMAC_DRBG_Reseed_algorithm
+    pub(crate) fn reseed(&mut self, entropy_input: &[u8], additional_input: &[u8]) {
+        self.update(&[entropy_input, additional_input]);
+        self.reseed_counter = 1;
+    }
+
+    /// HMAC_DRBG_Generate_algorithm
+    pub(crate) fn generate(&mut self, output: &mut [u8], additional_input: &[u8]) {
+        if self.reseed_counter > RESEED_INTERVAL {
+            panic!("DRBG reseed required")
+        }
+        if !additional_input.is_empty() {
+            self.update(&[additional_input]);
+        }
+        for chunk in output.chunks_mut(DIGEST_LEN) {
+            self.v = hmac_sha256(&self.key, &[&self.v]);
+            chunk.copy_from_slice(&self.v[..chunk.len()]);
+        }
+        self.update(&[additional_input]);
+        self.reseed_counter += 1;
+    }
+
+    pub(crate) fn seed_word(&self) -> u64 {
+        u64::from_le_bytes(self.v[..8].try_into().unwrap())
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        if self.index >= 8 {
+            let mut bytes = [0u8; 64];
+            self.generate(&mut bytes, &[]);
+            for (value, chunk) in self.block.iter_mut().zip(bytes.chunks_exact(8)) {
+                *value = u64::from_le_bytes(chunk.try_into().unwrap());
+            }
+            self.index = 0;
+        }
+        let value = self.block[self.index];
+        self.index += 1;
+        value
+    }
+
+    // HMAC_DRBG_Update over the concatenation of `provided_data`
+    fn update(&mut self, provided_data: &[&[u8]]) {
+        self.update_round(0x00, provided_data);
+        if provided_data.iter().any(|data| !data.is_empty()) {
+            self.update_round(0x01, provided_data);
+        }
+    }
+
+    // K = HMAC(K, V || separator || provided_data), V = HMAC(K, V)
+    fn update_round(&mut self, separator: u8, provided_data: &[&[u8]]) {
+        let mut parts: [&[u8]; 5] = [&self.v, &[separator], &[], &[], &[]];
+        for (part, data) in parts[2..].iter_mut().zip(provided_data) {
+            *part = data;
+        }
+        self.key = hmac_sha256(&self.key, &parts);
+        self.v = hmac_sha256(&self.key, &[&self.v]);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl HmacDrbg {
+    /// Overwrite K, V and the buffered outputs with zeros (requires `zeroize`
+    /// feature), as happens on drop
+    pub fn zeroize(&mut self) {
+        wipe(&mut self.key, [0; DIGEST_LEN]);
+        wipe(&mut self.v, [0; DIGEST_LEN]);
+        wipe(&mut self.block, [0; 8]);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for HmacDrbg {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl_portable!(HmacDrbg { key, v, reseed_counter, block, index } if |this| this.index <= 8);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::decode_hex;
+
+    #[test]
+    /// Instantiate, generate and reseed match OpenSSL's HMAC-DRBG (SHA-256)
+    fn test_matches_openssl() {
+        let entropy: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let nonce: [u8; 16] = core::array::from_fn(|i| 0x20 + i as u8);
+        let mut drbg = HmacDrbg::instantiate(&entropy, &nonce, b"pers!");
+        let mut output = [0u8; 64];
+
+        drbg.generate(&mut output, &[]);
+        assert_eq!(
+            output,
+            decode_hex::<64>(
+                "39c69dac824a7c812ab09a335f97e781560943baa15d96b3dd00f19c6709f757\
+                 fa41569103b504c9c9a8888c2b6ee749fcbfa27a30766c76a3aaa124a241b7c0"
+            )
+        );
+
+        drbg.generate(&mut output, &[1, 2, 3, 4]);
+        assert_eq!(
+            output,
+            decode_hex::<64>(
+                "e779bc5aac3ba6d5f5daae13bf8f75a02450e6b6db09f5c2c865c75941520cc8\
+                 d83655ea598421f777090c81eebad0ea84df2b51fbc4735bf38a627ce60860f9"
+            )
+        );
+
+        // OpenSSL reseeds with the supplied input, then again from its parent source
+        let fresh: [u8; 32] = core::array::from_fn(|i| 0x80 + i as u8);
+        drbg.reseed(&fresh, &[1, 2, 3, 4]);
+        drbg.reseed(&entropy, &[]);
+        drbg.generate(&mut output, &[]);
+        assert_eq!(
+            output,
+            decode_hex::<64>(
+                "f94f13f3129e98323a650864bb234a5d28ded48986ff3f3854b1f30792be905a\
+                 db6cd7d03ca60de7974d02e08e0a990fd9c8564111e492c1c6fd6c8f02a0828e"
+            )
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use super::portable::impl_portable;
+
+/// JSF64, Bob Jenkins' small fast 64-bit generator
+///
+/// Four words of state, seeded with Jenkins' `raninit` procedure (a fixed first
+/// word, the seed in the others, then 20 warm-up rounds).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Jsf64 {
+    a: u64,
+    b: u64,
+    c: u64,
+    d: u64,
+}
+
+impl Jsf64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        let mut jsf = Self {
+            a: 0xf1ea5eed,
+            b: seed,
+            c: seed,
+            d: seed,
+        };
+        for _ in 0..20 {
+            jsf.next_u64();
+        }
+        jsf
+    }
+
+    pub(crate) fn seed_word(&self) -> u64 {
+        self.a ^ self.b ^ self.c ^ self.d
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        let e = self.a.wrapping_sub(self.b.rotate_left(7));
+        self.a = self.b ^ self.c.rotate_left(13);
+        self.b = self.c.wrapping_add(self.d.rotate_left(37));
+        self.c = self.d.wrapping_add(e);
+        self.d = e.wrapping_add(self.a);
+        self.d
+    }
+}
+
+impl_portable!(Jsf64 { a, b, c, d } if |this| [this.a, this.b, this.c, this.d] != [0; 4]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Output matches the reference `ranval` after `raninit(0)`
+    fn test_reference_output() {
+        let mut rng = Jsf64::new(0);
+        let expected = [
+            0x4b39c42db38fcdf5,
+            0xaee2c9e919833f29,
+            0x30611cd75d0254ce,
+            0x7fcfd4f0c54692bb,
+        ];
+        for value in expected {
+            assert_eq!(rng.next_u64(), value);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use super::portable::impl_portable;
+
+/// Linear Congruential Generator (LCG) with a single 64-bit word of state
+///
+/// The increment selects one of 2^63 streams, stream 0 being the default
+/// increment of 1.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lcg {
+    state: u64,
+    increment: u64,
+}
+
+impl Lcg {
+    pub(crate) const fn new(seed: u64) -> Self {
+        Self::with_stream(seed, 0)
+    }
+
+    pub(crate) const fn with_stream(seed: u64, stream: u64) -> Self {
+        Self {
+            state: seed,
+            increment: (stream << 1) | 1,
+        }
+    }
+
+    pub(crate) fn seed_word(&self) -> u64 {
+        self.state
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self
+            .state
+            .wrapping_mul(MULTIPLIER)
+            .wrapping_add(self.increment);
+        self.state
+    }
+
+    /// Step back one output, which is skipping ahead a full period of 2^64 less one
+    pub(crate) fn previous(&mut self) {
+        self.advance(u64::MAX);
+    }
+
+    /// Skip `delta` outputs in O(log delta) steps
+    pub(crate) fn advance(&mut self, delta: u64) {
+        self.state = advance_lcg(self.state, delta, MULTIPLIER, self.increment);
+    }
+}
+
+/// Multiplier shared by the LCG and the PCG state transition
+pub(crate) const MULTIPLIER: u64 = 6364136223846793005;
+
+/// Apply the LCG step `x * multiplier + increment` `delta` times in O(log delta)
+///
+/// Brown's "Random Number Generation with Arbitrary Strides": square the step
+/// while walking the bits of `delta`, composing the steps for the set bits.
+pub(crate) fn advance_lcg(state: u64, mut delta: u64, multiplier: u64, increment: u64) -> u64 {
+    let (mut acc_mult, mut acc_plus) = (1u64, 0u64);
+    let (mut cur_mult, mut cur_plus) = (multiplier, increment);
+    while delta > 0 {
+        if delta & 1 == 1 {
+            acc_mult = acc_mult.wrapping_mul(cur_mult);
+            acc_plus = acc_plus.wrapping_mul(cur_mult).wrapping_add(cur_plus);
+        }
+        cur_plus = cur_mult.wrapping_add(1).wrapping_mul(cur_plus);
+        cur_mult = cur_mult.wrapping_mul(cur_mult);
+        delta >>= 1;
+    }
+    acc_mult.wrapping_mul(state).wrapping_add(acc_plus)
+}
+
+/// 128-bit version of [`advance_lcg`]
+pub(crate) fn advance_lcg128(
+    state: u128,
+    mut delta: u128,
+    multiplier: u128,
+    increment: u128,
+) -> u128 {
+    let (mut acc_mult, mut acc_plus) = (1u128, 0u128);
+    let (mut cur_mult, mut cur_plus) = (multiplier, increment);
+    while delta > 0 {
+        if delta & 1 == 1 {
+            acc_mult = acc_mult.wrapping_mul(cur_mult);
+            acc_plus = acc_plus.wrapping_mul(cur_mult).wrapping_add(cur_plus);
+        }
+        cur_plus = cur_mult.wrapping_add(1).wrapping_mul(cur_plus);
+        cur_mult = cur_mult.wrapping_mul(cur_mult);
+        delta >>= 1;
+    }
+    acc_mult.wrapping_mul(state).wrapping_add(acc_plus)
+}
+
+impl_portable!(Lcg { state, increment } if |this| this.increment & 1 == 1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Advancing matches stepping one output at a time
+    fn test_advance() {
+        let mut stepped = Lcg::with_stream(5, 3);
+        let mut advanced = stepped.clone();
+        for _ in 0..1000 {
+            stepped.next_u64();
+        }
+        advanced.advance(1000);
+        assert_eq!(advanced, stepped);
+    }
+
+    #[test]
+    /// Stepping back undoes one output
+    fn test_previous() {
+        let mut rng = Lcg::with_stream(5, 3);
+        let start = rng.clone();
+        rng.next_u64();
+        rng.previous();
+        assert_eq!(rng, start);
+    }
+}
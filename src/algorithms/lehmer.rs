@@ -0,0 +1,97 @@
+use super::expand_seed;
+use super::lcg::advance_lcg128;
+use super::portable::impl_portable;
+
+// Multiplier from Lemire's lehmer64, tested by Steele and Vigna
+const MULTIPLIER: u128 = 0xda942042e4dd58b5;
+
+/// Lehmer multiplicative congruential generator (MCG) with 128 bits of state
+///
+/// One multiply per output, returning the high 64 bits of the state. The seed is
+/// expanded to 128 bits with SplitMix64 and forced odd, which gives the maximum
+/// period of 2^126.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mcg128 {
+    state: u128,
+}
+
+impl Mcg128 {
+    pub(crate) fn new(seed: u64) -> Self {
+        let [high, low] = expand_seed(seed);
+        Self {
+            state: ((high as u128) << 64 | low as u128) | 1,
+        }
+    }
+
+    pub(crate) fn seed_word(&self) -> u64 {
+        (self.state >> 64) as u64 ^ self.state as u64
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_mul(MULTIPLIER);
+        (self.state >> 64) as u64
+    }
+
+    /// Step back one output; the multiplier's order divides 2^128, so 2^128 - 1
+    /// steps ahead is one step back
+    pub(crate) fn previous(&mut self) {
+        self.state = advance_lcg128(self.state, u128::MAX, MULTIPLIER, 0);
+    }
+
+    /// Skip `delta` outputs in O(log delta) steps
+    pub(crate) fn advance(&mut self, delta: u64) {
+        self.state = advance_lcg128(self.state, delta as u128, MULTIPLIER, 0);
+    }
+}
+
+impl_portable!(Mcg128 { state } if |this| this.state & 1 == 1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Output matches the reference lehmer64 for the state 1
+    fn test_reference_output() {
+        let mut rng = Mcg128 { state: 1 };
+        let expected = [
+            0x0000000000000000,
+            0xbaa09ca73f3265b4,
+            0xdb76c43996e558d0,
+            0x5b3942a42b92b969,
+        ];
+        for value in expected {
+            assert_eq!(rng.next_u64(), value);
+        }
+    }
+
+    #[test]
+    /// Seeding always produces an odd state
+    fn test_state_is_odd() {
+        for seed in 0..16 {
+            assert_eq!(Mcg128::new(seed).state & 1, 1);
+        }
+    }
+
+    #[test]
+    /// Advancing matches stepping one output at a time
+    fn test_advance() {
+        let mut stepped = Mcg128::new(11);
+        let mut advanced = stepped.clone();
+        for _ in 0..1000 {
+            stepped.next_u64();
+        }
+        advanced.advance(1000);
+        assert_eq!(advanced, stepped);
+    }
+
+    #[test]
+    /// Stepping back undoes one output
+    fn test_previous() {
+        let mut rng = Mcg128::new(11);
+        let start = rng.clone();
+        rng.next_u64();
+        rng.previous();
+        assert_eq!(rng, start);
+    }
+}
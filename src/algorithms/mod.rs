@@ -0,0 +1,679 @@
+//! Generator backends selectable through [`Algorithm`].
+//!
+//! Each backend owns its full state in its own struct, from the single word of
+//! [`Lcg`] to the 512 words of ISAAC64, and [`State`] holds whichever one is
+//! active. Adding a generator means adding its struct with `new`, `seed_word`
+//! and `next_u64`, one `State` variant, its arms in the `match`es below, and
+//! an `impl_portable!` line plus a new tag in `portable`. There is no fixed
+//! state width to fit into.
+
+#[cfg(feature = "drbg")]
+mod aes;
+#[cfg(feature = "crypto")]
+mod chacha;
+#[cfg(feature = "drbg")]
+mod ctr_drbg;
+#[cfg(feature = "drbg")]
+mod hash_drbg;
+#[cfg(feature = "drbg")]
+mod hmac_drbg;
+#[cfg(feature = "isaac")]
+mod isaac;
+mod jsf;
+mod lcg;
+mod lehmer;
+mod msws;
+#[cfg(feature = "mt19937")]
+mod mt;
+mod mwc;
+#[cfg(feature = "pcg")]
+mod pcg;
+#[cfg(feature = "pcg")]
+mod pcg64;
+mod philox;
+pub(crate) mod portable;
+#[cfg(feature = "romu")]
+mod romu;
+mod sfc;
+#[cfg(any(feature = "crypto", feature = "drbg", feature = "fortuna"))]
+pub(crate) mod sha256;
+pub(crate) mod splitmix;
+mod threefry;
+mod wyrand;
+mod xoroshiro;
+mod xorshift;
+mod xoshiro;
+mod xoshiro128;
+#[cfg(feature = "simd")]
+mod xoshiro_x4;
+
+use crate::{Algorithm, RngAlgorithm, SeedableAlgorithm};
+#[cfg(feature = "crypto")]
+pub use chacha::{ChaCha, ChaCha8, ChaCha12, ChaCha20};
+#[cfg(feature = "drbg")]
+pub use ctr_drbg::CtrDrbg;
+#[cfg(feature = "drbg")]
+pub use hash_drbg::HashDrbg;
+#[cfg(feature = "drbg")]
+pub use hmac_drbg::HmacDrbg;
+#[cfg(feature = "isaac")]
+pub use isaac::Isaac64;
+pub use jsf::Jsf64;
+pub use lcg::Lcg;
+pub use lehmer::Mcg128;
+pub use msws::Msws;
+#[cfg(feature = "mt19937")]
+pub use mt::Mt19937_64;
+pub use mwc::Mwc256;
+#[cfg(feature = "pcg")]
+pub use pcg::Pcg;
+#[cfg(feature = "pcg")]
+pub use pcg64::Pcg64;
+pub use philox::Philox4x32;
+#[cfg(feature = "romu")]
+pub use romu::{RomuDuoJr, RomuTrio};
+pub use sfc::Sfc64;
+pub use splitmix::SplitMix64;
+pub use threefry::Threefry2x64;
+pub use wyrand::WyRand;
+pub use xoroshiro::Xoroshiro128Plus;
+pub use xorshift::Xorshift64Star;
+pub use xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "simd")]
+pub use xoshiro_x4::Xoshiro256PlusPlusX4;
+pub use xoshiro128::Xoshiro128PlusPlus;
+
+/// Shortest DRBG entropy input, matching the 256-bit security strength
+#[cfg(feature = "drbg")]
+pub(crate) const MIN_ENTROPY_LEN: usize = 32;
+
+// Implement the public generator traits on top of each backend's inherent
+// `new` and `next_u64`, plus `fill_u64` for block generators marked `=> fill`
+macro_rules! impl_algorithm {
+    ($($(#[$meta:meta])* $ty:ty $(=> $fill:ident)? $([$bytes:ident])?,)*) => {
+        $(
+            $(#[$meta])*
+            impl RngAlgorithm for $ty {
+                fn next_u64(&mut self) -> u64 {
+                    <$ty>::next_u64(self)
+                }
+
+                $(
+                    fn $fill(&mut self, dest: &mut [u64]) {
+                        <$ty>::fill_u64(self, dest)
+                    }
+                )?
+            }
+
+            $(#[$meta])*
+            impl SeedableAlgorithm for $ty {
+                fn from_seed(seed: u64) -> Self {
+                    <$ty>::new(seed)
+                }
+
+                $(
+                    fn $bytes(seed: [u8; 32]) -> Self {
+                        <$ty>::from_seed_bytes(seed)
+                    }
+                )?
+            }
+        )*
+    };
+}
+
+impl_algorithm! {
+    Lcg,
+    #[cfg(feature = "pcg")]
+    Pcg,
+    Xoshiro256PlusPlus [from_seed_bytes],
+    Xoroshiro128Plus,
+    SplitMix64,
+    Xorshift64Star,
+    #[cfg(feature = "pcg")]
+    Pcg64,
+    #[cfg(feature = "mt19937")]
+    Mt19937_64,
+    WyRand,
+    Sfc64,
+    Jsf64,
+    #[cfg(feature = "romu")]
+    RomuDuoJr,
+    #[cfg(feature = "romu")]
+    RomuTrio,
+    Mcg128,
+    Philox4x32,
+    Threefry2x64,
+    Mwc256,
+    Msws,
+    #[cfg(feature = "crypto")]
+    ChaCha20 => fill_u64 [from_seed_bytes],
+    #[cfg(feature = "crypto")]
+    ChaCha12 => fill_u64 [from_seed_bytes],
+    #[cfg(feature = "crypto")]
+    ChaCha8 => fill_u64 [from_seed_bytes],
+    #[cfg(feature = "drbg")]
+    CtrDrbg [from_seed_bytes],
+    #[cfg(feature = "drbg")]
+    HmacDrbg [from_seed_bytes],
+    #[cfg(feature = "drbg")]
+    HashDrbg [from_seed_bytes],
+    #[cfg(feature = "isaac")]
+    Isaac64,
+    #[cfg(feature = "simd")]
+    Xoshiro256PlusPlusX4 => fill_u64,
+    Xoshiro128PlusPlus,
+}
+
+/// Internal state of the selected algorithm
+// Large states stay inline, there is no allocator to box them in `no_std` builds.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum State {
+    Lcg(Lcg),
+    #[cfg(feature = "pcg")]
+    Pcg(Pcg),
+    Xoshiro256PlusPlus(Xoshiro256PlusPlus),
+    Xoroshiro128Plus(Xoroshiro128Plus),
+    SplitMix64(SplitMix64),
+    Xorshift64Star(Xorshift64Star),
+    #[cfg(feature = "pcg")]
+    Pcg64(Pcg64),
+    #[cfg(feature = "mt19937")]
+    Mt19937_64(Mt19937_64),
+    WyRand(WyRand),
+    Sfc64(Sfc64),
+    Jsf64(Jsf64),
+    #[cfg(feature = "romu")]
+    RomuDuoJr(RomuDuoJr),
+    #[cfg(feature = "romu")]
+    RomuTrio(RomuTrio),
+    Mcg128(Mcg128),
+    Philox4x32(Philox4x32),
+    Threefry2x64(Threefry2x64),
+    Mwc256(Mwc256),
+    Msws(Msws),
+    #[cfg(feature = "crypto")]
+    ChaCha20(ChaCha20),
+    #[cfg(feature = "crypto")]
+    ChaCha12(ChaCha12),
+    #[cfg(feature = "crypto")]
+    ChaCha8(ChaCha8),
+    #[cfg(feature = "drbg")]
+    CtrDrbg(CtrDrbg),
+    #[cfg(feature = "drbg")]
+    HmacDrbg(HmacDrbg),
+    #[cfg(feature = "drbg")]
+    HashDrbg(HashDrbg),
+    #[cfg(feature = "isaac")]
+    Isaac64(Isaac64),
+    Xoshiro128PlusPlus(Xoshiro128PlusPlus),
+}
+
+impl State {
+    /// Initialise the state of `algorithm` from a 64-bit seed
+    pub(crate) fn new(algorithm: Algorithm, seed: u64) -> Self {
+        match algorithm {
+            Algorithm::Lcg => State::Lcg(Lcg::new(seed)),
+            #[cfg(feature = "pcg")]
+            Algorithm::Pcg => State::Pcg(Pcg::new(seed)),
+            Algorithm::Xoshiro256PlusPlus => {
+                State::Xoshiro256PlusPlus(Xoshiro256PlusPlus::new(seed))
+            }
+            Algorithm::Xoroshiro128Plus => State::Xoroshiro128Plus(Xoroshiro128Plus::new(seed)),
+            Algorithm::SplitMix64 => State::SplitMix64(SplitMix64::new(seed)),
+            Algorithm::Xorshift64Star => State::Xorshift64Star(Xorshift64Star::new(seed)),
+            #[cfg(feature = "pcg")]
+            Algorithm::Pcg64 => State::Pcg64(Pcg64::new(seed)),
+            #[cfg(feature = "mt19937")]
+            Algorithm::Mt19937_64 => State::Mt19937_64(Mt19937_64::new(seed)),
+            Algorithm::WyRand => State::WyRand(WyRand::new(seed)),
+            Algorithm::Sfc64 => State::Sfc64(Sfc64::new(seed)),
+            Algorithm::Jsf64 => State::Jsf64(Jsf64::new(seed)),
+            #[cfg(feature = "romu")]
+            Algorithm::RomuDuoJr => State::RomuDuoJr(RomuDuoJr::new(seed)),
+            #[cfg(feature = "romu")]
+            Algorithm::RomuTrio => State::RomuTrio(RomuTrio::new(seed)),
+            Algorithm::Mcg128 => State::Mcg128(Mcg128::new(seed)),
+            Algorithm::Philox4x32 => State::Philox4x32(Philox4x32::new(seed)),
+            Algorithm::Threefry2x64 => State::Threefry2x64(Threefry2x64::new(seed)),
+            Algorithm::Mwc256 => State::Mwc256(Mwc256::new(seed)),
+            Algorithm::Msws => State::Msws(Msws::new(seed)),
+            #[cfg(feature = "crypto")]
+            Algorithm::ChaCha20 => State::ChaCha20(ChaCha20::new(seed)),
+            #[cfg(feature = "crypto")]
+            Algorithm::ChaCha12 => State::ChaCha12(ChaCha12::new(seed)),
+            #[cfg(feature = "crypto")]
+            Algorithm::ChaCha8 => State::ChaCha8(ChaCha8::new(seed)),
+            #[cfg(feature = "drbg")]
+            Algorithm::CtrDrbg => State::CtrDrbg(CtrDrbg::new(seed)),
+            #[cfg(feature = "drbg")]
+            Algorithm::HmacDrbg => State::HmacDrbg(HmacDrbg::new(seed)),
+            #[cfg(feature = "drbg")]
+            Algorithm::HashDrbg => State::HashDrbg(HashDrbg::new(seed)),
+            #[cfg(feature = "isaac")]
+            Algorithm::Isaac64 => State::Isaac64(Isaac64::new(seed)),
+            Algorithm::Xoshiro128PlusPlus => {
+                State::Xoshiro128PlusPlus(Xoshiro128PlusPlus::new(seed))
+            }
+        }
+    }
+
+    /// Seed `algorithm` on the given stream, returning `None` if it has none
+    pub(crate) fn with_stream(algorithm: Algorithm, seed: u64, stream: u64) -> Option<Self> {
+        Some(match algorithm {
+            Algorithm::Lcg => State::Lcg(Lcg::with_stream(seed, stream)),
+            #[cfg(feature = "pcg")]
+            Algorithm::Pcg => State::Pcg(Pcg::with_stream(seed, stream)),
+            #[cfg(feature = "pcg")]
+            Algorithm::Pcg64 => State::Pcg64(Pcg64::with_stream(seed as u128, stream as u128)),
+            Algorithm::Philox4x32 => State::Philox4x32(Philox4x32::with_stream(seed, stream)),
+            Algorithm::Threefry2x64 => State::Threefry2x64(Threefry2x64::with_stream(seed, stream)),
+            #[cfg(feature = "crypto")]
+            Algorithm::ChaCha20 => State::ChaCha20(ChaCha20::with_stream(seed, stream)),
+            #[cfg(feature = "crypto")]
+            Algorithm::ChaCha12 => State::ChaCha12(ChaCha12::with_stream(seed, stream)),
+            #[cfg(feature = "crypto")]
+            Algorithm::ChaCha8 => State::ChaCha8(ChaCha8::with_stream(seed, stream)),
+            _ => return None,
+        })
+    }
+
+    /// The algorithm this state belongs to
+    pub(crate) fn algorithm(&self) -> Algorithm {
+        match self {
+            State::Lcg(_) => Algorithm::Lcg,
+            #[cfg(feature = "pcg")]
+            State::Pcg(_) => Algorithm::Pcg,
+            State::Xoshiro256PlusPlus(_) => Algorithm::Xoshiro256PlusPlus,
+            State::Xoroshiro128Plus(_) => Algorithm::Xoroshiro128Plus,
+            State::SplitMix64(_) => Algorithm::SplitMix64,
+            State::Xorshift64Star(_) => Algorithm::Xorshift64Star,
+            #[cfg(feature = "pcg")]
+            State::Pcg64(_) => Algorithm::Pcg64,
+            #[cfg(feature = "mt19937")]
+            State::Mt19937_64(_) => Algorithm::Mt19937_64,
+            State::WyRand(_) => Algorithm::WyRand,
+            State::Sfc64(_) => Algorithm::Sfc64,
+            State::Jsf64(_) => Algorithm::Jsf64,
+            #[cfg(feature = "romu")]
+            State::RomuDuoJr(_) => Algorithm::RomuDuoJr,
+            #[cfg(feature = "romu")]
+            State::RomuTrio(_) => Algorithm::RomuTrio,
+            State::Mcg128(_) => Algorithm::Mcg128,
+            State::Philox4x32(_) => Algorithm::Philox4x32,
+            State::Threefry2x64(_) => Algorithm::Threefry2x64,
+            State::Mwc256(_) => Algorithm::Mwc256,
+            State::Msws(_) => Algorithm::Msws,
+            #[cfg(feature = "crypto")]
+            State::ChaCha20(_) => Algorithm::ChaCha20,
+            #[cfg(feature = "crypto")]
+            State::ChaCha12(_) => Algorithm::ChaCha12,
+            #[cfg(feature = "crypto")]
+            State::ChaCha8(_) => Algorithm::ChaCha8,
+            #[cfg(feature = "drbg")]
+            State::CtrDrbg(_) => Algorithm::CtrDrbg,
+            #[cfg(feature = "drbg")]
+            State::HmacDrbg(_) => Algorithm::HmacDrbg,
+            #[cfg(feature = "drbg")]
+            State::HashDrbg(_) => Algorithm::HashDrbg,
+            #[cfg(feature = "isaac")]
+            State::Isaac64(_) => Algorithm::Isaac64,
+            State::Xoshiro128PlusPlus(_) => Algorithm::Xoshiro128PlusPlus,
+        }
+    }
+
+    /// Fold the current state into a single word, used to seed another algorithm
+    pub(crate) fn seed_word(&self) -> u64 {
+        match self {
+            State::Lcg(lcg) => lcg.seed_word(),
+            #[cfg(feature = "pcg")]
+            State::Pcg(pcg) => pcg.seed_word(),
+            State::Xoshiro256PlusPlus(xoshiro) => xoshiro.seed_word(),
+            State::Xoroshiro128Plus(xoroshiro) => xoroshiro.seed_word(),
+            State::SplitMix64(splitmix) => splitmix.seed_word(),
+            State::Xorshift64Star(xorshift) => xorshift.seed_word(),
+            #[cfg(feature = "pcg")]
+            State::Pcg64(pcg64) => pcg64.seed_word(),
+            #[cfg(feature = "mt19937")]
+            State::Mt19937_64(mt) => mt.seed_word(),
+            State::WyRand(wyrand) => wyrand.seed_word(),
+            State::Sfc64(sfc) => sfc.seed_word(),
+            State::Jsf64(jsf) => jsf.seed_word(),
+            #[cfg(feature = "romu")]
+            State::RomuDuoJr(romu) => romu.seed_word(),
+            #[cfg(feature = "romu")]
+            State::RomuTrio(romu) => romu.seed_word(),
+            State::Mcg128(lehmer) => lehmer.seed_word(),
+            State::Philox4x32(philox) => philox.seed_word(),
+            State::Threefry2x64(threefry) => threefry.seed_word(),
+            State::Mwc256(mwc) => mwc.seed_word(),
+            State::Msws(msws) => msws.seed_word(),
+            #[cfg(feature = "crypto")]
+            State::ChaCha20(chacha) => chacha.seed_word(),
+            #[cfg(feature = "crypto")]
+            State::ChaCha12(chacha) => chacha.seed_word(),
+            #[cfg(feature = "crypto")]
+            State::ChaCha8(chacha) => chacha.seed_word(),
+            #[cfg(feature = "drbg")]
+            State::CtrDrbg(ctr_drbg) => ctr_drbg.seed_word(),
+            #[cfg(feature = "drbg")]
+            State::HmacDrbg(hmac_drbg) => hmac_drbg.seed_word(),
+            #[cfg(feature = "drbg")]
+            State::HashDrbg(hash_drbg) => hash_drbg.seed_word(),
+            #[cfg(feature = "isaac")]
+            State::Isaac64(isaac) => isaac.seed_word(),
+            State::Xoshiro128PlusPlus(xoshiro128) => xoshiro128.seed_word(),
+        }
+    }
+
+    /// Fill `dest` with consecutive outputs, in whole blocks where the backend has them
+    pub(crate) fn fill_u64(&mut self, dest: &mut [u64]) {
+        match self {
+            #[cfg(feature = "crypto")]
+            State::ChaCha20(chacha) => chacha.fill_u64(dest),
+            #[cfg(feature = "crypto")]
+            State::ChaCha12(chacha) => chacha.fill_u64(dest),
+            #[cfg(feature = "crypto")]
+            State::ChaCha8(chacha) => chacha.fill_u64(dest),
+            _ => {
+                for word in dest {
+                    *word = self.next_u64();
+                }
+            }
+        }
+    }
+
+    /// Skip `delta` outputs of an LCG-class algorithm, returning `false` if it can't
+    pub(crate) fn advance(&mut self, delta: u64) -> bool {
+        match self {
+            State::Lcg(lcg) => lcg.advance(delta),
+            #[cfg(feature = "pcg")]
+            State::Pcg(pcg) => pcg.advance(delta),
+            #[cfg(feature = "pcg")]
+            State::Pcg64(pcg64) => pcg64.advance(delta),
+            State::Mcg128(lehmer) => lehmer.advance(delta),
+            State::SplitMix64(splitmix) => splitmix.advance(delta),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Step an invertible algorithm back one output, returning `false` if it can't
+    pub(crate) fn previous(&mut self) -> bool {
+        match self {
+            State::Lcg(lcg) => lcg.previous(),
+            #[cfg(feature = "pcg")]
+            State::Pcg(pcg) => pcg.previous(),
+            #[cfg(feature = "pcg")]
+            State::Pcg64(pcg64) => pcg64.previous(),
+            State::Mcg128(lehmer) => lehmer.previous(),
+            State::SplitMix64(splitmix) => splitmix.previous(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Jump a xoshiro-class algorithm ahead, returning `false` if it has no jump
+    pub(crate) fn jump(&mut self, long: bool) -> bool {
+        match self {
+            State::Xoshiro256PlusPlus(xoshiro) if long => xoshiro.long_jump(),
+            State::Xoshiro256PlusPlus(xoshiro) => xoshiro.jump(),
+            State::Xoroshiro128Plus(xoroshiro) if long => xoroshiro.long_jump(),
+            State::Xoroshiro128Plus(xoroshiro) => xoroshiro.jump(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Jump a counter-based algorithm to the output at `index`
+    ///
+    /// Returns `false` if the algorithm is not counter-based.
+    pub(crate) fn seek(&mut self, index: u64) -> bool {
+        match self {
+            State::Philox4x32(philox) => philox.seek(index),
+            State::Threefry2x64(threefry) => threefry.seek(index),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Key a cryptographic `algorithm` with all 32 bytes of `key`, or `None` for
+    /// the other algorithms
+    #[cfg(any(feature = "crypto", feature = "drbg"))]
+    pub(crate) fn from_key(algorithm: Algorithm, key: [u8; 32]) -> Option<Self> {
+        Some(match algorithm {
+            #[cfg(feature = "crypto")]
+            Algorithm::ChaCha20 => State::ChaCha20(ChaCha20::from_seed_bytes(key)),
+            #[cfg(feature = "crypto")]
+            Algorithm::ChaCha12 => State::ChaCha12(ChaCha12::from_seed_bytes(key)),
+            #[cfg(feature = "crypto")]
+            Algorithm::ChaCha8 => State::ChaCha8(ChaCha8::from_seed_bytes(key)),
+            #[cfg(feature = "drbg")]
+            Algorithm::CtrDrbg => State::CtrDrbg(CtrDrbg::from_seed_bytes(key)),
+            #[cfg(feature = "drbg")]
+            Algorithm::HmacDrbg => State::HmacDrbg(HmacDrbg::from_seed_bytes(key)),
+            #[cfg(feature = "drbg")]
+            Algorithm::HashDrbg => State::HashDrbg(HashDrbg::from_seed_bytes(key)),
+            _ => return None,
+        })
+    }
+
+    /// Instantiate a DRBG algorithm, or `None` if `algorithm` is not a DRBG
+    #[cfg(feature = "drbg")]
+    pub(crate) fn instantiate_drbg(
+        algorithm: Algorithm,
+        entropy_input: &[u8],
+        nonce: &[u8],
+        personalization: &[u8],
+    ) -> Option<Self> {
+        match algorithm {
+            Algorithm::CtrDrbg => Some(State::CtrDrbg(CtrDrbg::instantiate(
+                entropy_input,
+                nonce,
+                personalization,
+            ))),
+            Algorithm::HmacDrbg => Some(State::HmacDrbg(HmacDrbg::instantiate(
+                entropy_input,
+                nonce,
+                personalization,
+            ))),
+            Algorithm::HashDrbg => Some(State::HashDrbg(HashDrbg::instantiate(
+                entropy_input,
+                nonce,
+                personalization,
+            ))),
+            _ => None,
+        }
+    }
+
+    /// Reseed a DRBG algorithm, returning `false` if the state is not a DRBG
+    #[cfg(feature = "drbg")]
+    pub(crate) fn reseed_drbg(&mut self, entropy_input: &[u8], additional_input: &[u8]) -> bool {
+        match self {
+            State::CtrDrbg(drbg) => drbg.reseed(entropy_input, additional_input),
+            State::HmacDrbg(drbg) => drbg.reseed(entropy_input, additional_input),
+            State::HashDrbg(drbg) => drbg.reseed(entropy_input, additional_input),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Mix `input` into the full key of a cryptographic state, returning `false`
+    /// for the other algorithms
+    ///
+    /// DRBGs take `input` as the entropy input of their SP 800-90A reseed, and
+    /// ChaCha hashes it with its current key into a new 256-bit key. Input
+    /// shorter than the 32 bytes SP 800-90A asks for is first conditioned with
+    /// SHA-256; that meets the length rule but adds no entropy beyond what the
+    /// input had.
+    #[cfg(any(feature = "crypto", feature = "drbg"))]
+    pub(crate) fn rekey(&mut self, input: &[u8]) -> bool {
+        match self {
+            #[cfg(feature = "crypto")]
+            State::ChaCha20(chacha) => chacha.rekey(input),
+            #[cfg(feature = "crypto")]
+            State::ChaCha12(chacha) => chacha.rekey(input),
+            #[cfg(feature = "crypto")]
+            State::ChaCha8(chacha) => chacha.rekey(input),
+            #[cfg(feature = "drbg")]
+            _ if input.len() >= MIN_ENTROPY_LEN => self.reseed_drbg(input, &[]),
+            #[cfg(feature = "drbg")]
+            _ => {
+                let mut hash = sha256::Sha256::new();
+                hash.update(input);
+                self.reseed_drbg(&hash.finalize(), &[])
+            }
+            #[cfg(not(feature = "drbg"))]
+            _ => false,
+        }
+    }
+
+    /// Turn a cryptographic state into the one for worker `worker_index`, keyed
+    /// from its full key and position, returning `false` for the other
+    /// algorithms
+    ///
+    /// ChaCha hashes its key, stream, position and the index into a new key
+    /// and moves to stream `worker_index`. DRBGs reseed from SHA-256 of their
+    /// encoded state and the index.
+    #[cfg(any(feature = "crypto", feature = "drbg"))]
+    pub(crate) fn rekey_worker(&mut self, worker_index: u64) -> bool {
+        match self {
+            #[cfg(feature = "crypto")]
+            State::ChaCha20(chacha) => chacha.rekey_worker(worker_index),
+            #[cfg(feature = "crypto")]
+            State::ChaCha12(chacha) => chacha.rekey_worker(worker_index),
+            #[cfg(feature = "crypto")]
+            State::ChaCha8(chacha) => chacha.rekey_worker(worker_index),
+            #[cfg(feature = "drbg")]
+            _ => {
+                let mut hash = sha256::Sha256::new();
+                portable::Portable::encode(self, &mut |piece| hash.update(piece));
+                hash.update(&worker_index.to_le_bytes());
+                self.reseed_drbg(&hash.finalize(), &[])
+            }
+            #[cfg(not(feature = "drbg"))]
+            _ => false,
+        }
+    }
+
+    /// Reseed from 32 bytes of fresh material
+    ///
+    /// DRBGs and ChaCha mix the material into their full key through
+    /// [`State::rekey`]. Every other state is rebuilt from the material folded
+    /// into its next output, so a reseed never discards the entropy the state
+    /// already had.
+    #[cfg(feature = "fortuna")]
+    pub(crate) fn reseed(&mut self, material: &[u8; 32]) {
+        #[cfg(any(feature = "crypto", feature = "drbg"))]
+        if self.rekey(material) {
+            return;
+        }
+        let seed = material
+            .chunks_exact(8)
+            .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+            .fold(self.next_u64(), |acc, word| acc ^ word);
+        *self = State::new(self.algorithm(), seed);
+    }
+
+    /// Run one DRBG generate request, returning `false` if the state is not a DRBG
+    #[cfg(feature = "drbg")]
+    pub(crate) fn generate_drbg(&mut self, output: &mut [u8], additional_input: &[u8]) -> bool {
+        match self {
+            State::CtrDrbg(drbg) => drbg.generate(output, additional_input),
+            State::HmacDrbg(drbg) => drbg.generate(output, additional_input),
+            State::HashDrbg(drbg) => drbg.generate(output, additional_input),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Advance the state and return the next output
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        match self {
+            State::Lcg(lcg) => lcg.next_u64(),
+            #[cfg(feature = "pcg")]
+            State::Pcg(pcg) => pcg.next_u64(),
+            State::Xoshiro256PlusPlus(xoshiro) => xoshiro.next_u64(),
+            State::Xoroshiro128Plus(xoroshiro) => xoroshiro.next_u64(),
+            State::SplitMix64(splitmix) => splitmix.next_u64(),
+            State::Xorshift64Star(xorshift) => xorshift.next_u64(),
+            #[cfg(feature = "pcg")]
+            State::Pcg64(pcg64) => pcg64.next_u64(),
+            #[cfg(feature = "mt19937")]
+            State::Mt19937_64(mt) => mt.next_u64(),
+            State::WyRand(wyrand) => wyrand.next_u64(),
+            State::Sfc64(sfc) => sfc.next_u64(),
+            State::Jsf64(jsf) => jsf.next_u64(),
+            #[cfg(feature = "romu")]
+            State::RomuDuoJr(romu) => romu.next_u64(),
+            #[cfg(feature = "romu")]
+            State::RomuTrio(romu) => romu.next_u64(),
+            State::Mcg128(lehmer) => lehmer.next_u64(),
+            State::Philox4x32(philox) => philox.next_u64(),
+            State::Threefry2x64(threefry) => threefry.next_u64(),
+            State::Mwc256(mwc) => mwc.next_u64(),
+            State::Msws(msws) => msws.next_u64(),
+            #[cfg(feature = "crypto")]
+            State::ChaCha20(chacha) => chacha.next_u64(),
+            #[cfg(feature = "crypto")]
+            State::ChaCha12(chacha) => chacha.next_u64(),
+            #[cfg(feature = "crypto")]
+            State::ChaCha8(chacha) => chacha.next_u64(),
+            #[cfg(feature = "drbg")]
+            State::CtrDrbg(ctr_drbg) => ctr_drbg.next_u64(),
+            #[cfg(feature = "drbg")]
+            State::HmacDrbg(hmac_drbg) => hmac_drbg.next_u64(),
+            #[cfg(feature = "drbg")]
+            State::HashDrbg(hash_drbg) => hash_drbg.next_u64(),
+            #[cfg(feature = "isaac")]
+            State::Isaac64(isaac) => isaac.next_u64(),
+            State::Xoshiro128PlusPlus(xoshiro128) => xoshiro128.next_u64(),
+        }
+    }
+}
+
+/// Expand a 64-bit seed into `N` words of state using SplitMix64
+///
+/// Consecutive SplitMix64 outputs are distinct, so the result is never all zeros.
+pub(crate) fn expand_seed<const N: usize>(seed: u64) -> [u64; N] {
+    let mut splitmix = SplitMix64::new(seed);
+    let mut words = [0u64; N];
+    for word in &mut words {
+        *word = splitmix.next_u64();
+    }
+    words
+}
+
+/// Fold a 32-byte seed into one word, mixing after each 8-byte word so every
+/// byte affects the result
+pub(crate) fn fold_seed(seed: &[u8; 32]) -> u64 {
+    let (words, _) = seed.as_chunks::<8>();
+    words.iter().fold(0, |folded, word| {
+        let [mixed] = expand_seed(folded ^ u64::from_le_bytes(*word));
+        mixed
+    })
+}
+
+/// Overwrite `value` with `zero` through a volatile write the compiler cannot
+/// remove, for wiping key material
+#[cfg(all(
+    feature = "zeroize",
+    any(feature = "crypto", feature = "drbg", feature = "fortuna")
+))]
+pub(crate) fn wipe<T: Copy>(value: &mut T, zero: T) {
+    // SAFETY: `value` is a valid, aligned and exclusive reference
+    unsafe { core::ptr::write_volatile(value, zero) };
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+/// Decode a hex string into a fixed-size byte array
+#[cfg(all(test, any(feature = "crypto", feature = "drbg", feature = "fortuna")))]
+pub(crate) fn decode_hex<const N: usize>(hex: &str) -> [u8; N] {
+    let mut bytes = [0u8; N];
+    assert_eq!(hex.len(), 2 * N);
+    for (byte, pair) in bytes.iter_mut().zip(hex.as_bytes().chunks_exact(2)) {
+        let text = core::str::from_utf8(pair).unwrap();
+        *byte = u8::from_str_radix(text, 16).unwrap();
+    }
+    bytes
+}
@@ -0,0 +1,129 @@
+use super::lcg::{MULTIPLIER, advance_lcg};
+use super::portable::impl_portable;
+
+// Increment of the reference PCG32 initializer, used when no stream is chosen
+const DEFAULT_INCREMENT: u64 = 0xda3e39cb94b95bdb;
+
+/// Permuted Congruential Generator (PCG-XSH-RR 64/32)
+///
+/// Keeps a 64-bit LCG state plus an odd increment selecting the stream. The state
+/// only ever advances as an LCG; the permutation is applied to the output, so each
+/// `u64` is built from two 32-bit outputs (first one in the low half).
+/// Only available with the `pcg` feature.
+///
+/// # Example
+/// ```rust
+/// use simple_rng::Algorithm::Pcg;
+/// use simple_rng::RNG;
+/// let mut rng = RNG::from_time();
+/// rng.set_algorithm(Pcg);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pcg {
+    state: u64,
+    increment: u64,
+}
+
+impl Pcg {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self::with_stream(seed, DEFAULT_INCREMENT >> 1)
+    }
+
+    /// Seed like the reference `pcg32_srandom_r(seed, stream)`
+    pub(crate) fn with_stream(seed: u64, stream: u64) -> Self {
+        let mut pcg = Self {
+            state: 0,
+            increment: (stream << 1) | 1,
+        };
+        pcg.step();
+        pcg.state = pcg.state.wrapping_add(seed);
+        pcg.step();
+        pcg
+    }
+
+    pub(crate) fn seed_word(&self) -> u64 {
+        self.state
+    }
+
+    pub(crate) fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.step();
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        let rot = (old >> 59) as u32;
+        xorshifted.rotate_right(rot)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        let low = self.next_u32() as u64;
+        let high = self.next_u32() as u64;
+        (high << 32) | low
+    }
+
+    /// Step back one output, which is skipping ahead a full period of 2^64 less one
+    pub(crate) fn previous(&mut self) {
+        self.advance(u64::MAX);
+    }
+
+    /// Skip `delta` u64 outputs, two state steps each, in O(log delta)
+    pub(crate) fn advance(&mut self, delta: u64) {
+        let steps = delta.wrapping_mul(2);
+        self.state = advance_lcg(self.state, steps, MULTIPLIER, self.increment);
+    }
+
+    fn step(&mut self) {
+        self.state = self
+            .state
+            .wrapping_mul(MULTIPLIER)
+            .wrapping_add(self.increment);
+    }
+}
+
+impl_portable!(Pcg { state, increment } if |this| this.increment & 1 == 1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Output matches the reference pcg32 demo seeded with (42, 54)
+    fn test_reference_output() {
+        let mut rng = Pcg::with_stream(42, 54);
+        let expected = [
+            0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e,
+        ];
+        for value in expected {
+            assert_eq!(rng.next_u32(), value);
+        }
+    }
+
+    #[test]
+    /// The increment is odd and the default stream matches the reference initializer
+    fn test_default_stream() {
+        let mut rng = Pcg::new(42);
+        assert_eq!(rng.increment, DEFAULT_INCREMENT);
+        assert_eq!(rng.next_u64(), 0x3237b41c_ddaa6c75);
+        assert_eq!(rng.next_u64(), 0xc17a7979_e070ca56);
+    }
+
+    #[test]
+    /// Advancing matches stepping one output at a time
+    fn test_advance() {
+        let mut stepped = Pcg::with_stream(42, 54);
+        let mut advanced = stepped.clone();
+        for _ in 0..1000 {
+            stepped.next_u64();
+        }
+        advanced.advance(1000);
+        assert_eq!(advanced, stepped);
+    }
+
+    #[test]
+    /// Stepping back undoes one output
+    fn test_previous() {
+        let mut rng = Pcg::with_stream(42, 54);
+        let start = rng.clone();
+        rng.next_u64();
+        rng.previous();
+        assert_eq!(rng, start);
+    }
+}
@@ -0,0 +1,169 @@
+//! Stable byte encoding of generator states, used by [`crate::RNG::to_bytes`].
+//!
+//! Every value is written little-endian in declaration order, `usize` as a
+//! `u64`, so the encoding is the same on every platform. Each algorithm has a
+//! fixed tag that never changes, whatever features are enabled. New
+//! algorithms take new tags, and a change to an existing layout needs a new
+//! format version.
+
+use super::*;
+
+/// A value with a stable byte encoding
+pub(crate) trait Portable: Sized {
+    /// Pass the encoding of `self` to `out`, in one or more pieces
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    fn encode(&self, out: &mut dyn FnMut(&[u8]));
+
+    /// Read a value from the front of `input`, or `None` if it is invalid
+    fn decode(input: &mut &[u8]) -> Option<Self>;
+}
+
+macro_rules! impl_portable_int {
+    ($($ty:ty),*) => {$(
+        impl Portable for $ty {
+            fn encode(&self, out: &mut dyn FnMut(&[u8])) {
+                out(&self.to_le_bytes())
+            }
+
+            fn decode(input: &mut &[u8]) -> Option<Self> {
+                let (bytes, rest) = input.split_first_chunk()?;
+                *input = rest;
+                Some(<$ty>::from_le_bytes(*bytes))
+            }
+        }
+    )*};
+}
+
+impl_portable_int!(u8, u32, u64, u128);
+
+impl Portable for usize {
+    fn encode(&self, out: &mut dyn FnMut(&[u8])) {
+        (*self as u64).encode(out)
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        u64::decode(input)?.try_into().ok()
+    }
+}
+
+impl<T: Portable + Copy, const N: usize> Portable for [T; N] {
+    fn encode(&self, out: &mut dyn FnMut(&[u8])) {
+        for value in self {
+            value.encode(out);
+        }
+    }
+
+    // The first element fills the array until the rest are decoded, so the
+    // encoded arrays are never empty
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        let first = T::decode(input)?;
+        let mut values = [first; N];
+        for value in values.iter_mut().skip(1) {
+            *value = T::decode(input)?;
+        }
+        Some(values)
+    }
+}
+
+/// Implement [`Portable`] for a struct by encoding the listed fields in order
+///
+/// A `<const N>` parameter covers generators generic over a `usize`, such as
+/// the ChaCha round count.
+///
+/// An optional `if` condition on the decoded value `$this` rejects states the
+/// generator could not run from, such as an out-of-range buffer index, an
+/// even LCG increment or an all-zero xorshift state.
+macro_rules! impl_portable {
+    ($ty:ident $(<const $param:ident>)? { $($field:ident),* } $(if |$this:ident| $check:expr)?) => {
+        impl$(<const $param: usize>)? $crate::algorithms::portable::Portable for $ty$(<$param>)? {
+            fn encode(&self, out: &mut dyn FnMut(&[u8])) {
+                $($crate::algorithms::portable::Portable::encode(&self.$field, out);)*
+            }
+
+            fn decode(input: &mut &[u8]) -> Option<Self> {
+                let value = Self {
+                    $($field: $crate::algorithms::portable::Portable::decode(input)?,)*
+                };
+                $(
+                    let $this = &value;
+                    if !$check {
+                        return None;
+                    }
+                )?
+                Some(value)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_portable;
+
+// Tag every `State` variant and encode it as the tag followed by the state
+macro_rules! impl_portable_state {
+    ($($(#[$meta:meta])* $tag:literal $name:ident,)*) => {
+        impl Portable for State {
+            fn encode(&self, out: &mut dyn FnMut(&[u8])) {
+                match self {
+                    $(
+                        $(#[$meta])*
+                        State::$name(state) => {
+                            out(&[$tag]);
+                            state.encode(out);
+                        }
+                    )*
+                }
+            }
+
+            fn decode(input: &mut &[u8]) -> Option<Self> {
+                Some(match u8::decode(input)? {
+                    $(
+                        $(#[$meta])*
+                        $tag => State::$name(Portable::decode(input)?),
+                    )*
+                    _ => return None,
+                })
+            }
+        }
+    };
+}
+
+impl_portable_state! {
+    0 Lcg,
+    #[cfg(feature = "pcg")]
+    1 Pcg,
+    2 Xoshiro256PlusPlus,
+    3 Xoroshiro128Plus,
+    4 SplitMix64,
+    5 Xorshift64Star,
+    #[cfg(feature = "pcg")]
+    6 Pcg64,
+    #[cfg(feature = "mt19937")]
+    7 Mt19937_64,
+    8 WyRand,
+    9 Sfc64,
+    10 Jsf64,
+    #[cfg(feature = "romu")]
+    11 RomuDuoJr,
+    #[cfg(feature = "romu")]
+    12 RomuTrio,
+    13 Mcg128,
+    14 Philox4x32,
+    15 Threefry2x64,
+    16 Mwc256,
+    17 Msws,
+    #[cfg(feature = "crypto")]
+    18 ChaCha20,
+    #[cfg(feature = "crypto")]
+    19 ChaCha12,
+    #[cfg(feature = "crypto")]
+    20 ChaCha8,
+    #[cfg(feature = "drbg")]
+    21 CtrDrbg,
+    #[cfg(feature = "drbg")]
+    22 HmacDrbg,
+    #[cfg(feature = "drbg")]
+    23 HashDrbg,
+    #[cfg(feature = "isaac")]
+    24 Isaac64,
+    25 Xoshiro128PlusPlus,
+}
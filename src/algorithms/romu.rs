@@ -0,0 +1,101 @@
+use super::expand_seed;
+use super::portable::impl_portable;
+
+// Multiplier shared by the Romu generators
+const MULTIPLIER: u64 = 15241094284759029579;
+
+/// RomuDuoJr by Mark A. Overton (requires `romu` feature)
+///
+/// Two words of state, the fastest of the Romu family. The seed is expanded with
+/// SplitMix64 so the state is never all zeros.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RomuDuoJr {
+    x: u64,
+    y: u64,
+}
+
+impl RomuDuoJr {
+    pub(crate) fn new(seed: u64) -> Self {
+        let [x, y] = expand_seed(seed);
+        Self { x, y }
+    }
+
+    pub(crate) fn seed_word(&self) -> u64 {
+        self.x ^ self.y
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        let xp = self.x;
+        self.x = MULTIPLIER.wrapping_mul(self.y);
+        self.y = self.y.wrapping_sub(xp).rotate_left(27);
+        xp
+    }
+}
+
+/// RomuTrio by Mark A. Overton (requires `romu` feature)
+///
+/// Three words of state, recommended by the author for general use. The seed is
+/// expanded with SplitMix64 so the state is never all zeros.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RomuTrio {
+    x: u64,
+    y: u64,
+    z: u64,
+}
+
+impl RomuTrio {
+    pub(crate) fn new(seed: u64) -> Self {
+        let [x, y, z] = expand_seed(seed);
+        Self { x, y, z }
+    }
+
+    pub(crate) fn seed_word(&self) -> u64 {
+        self.x ^ self.y ^ self.z
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        let (xp, yp, zp) = (self.x, self.y, self.z);
+        self.x = MULTIPLIER.wrapping_mul(zp);
+        self.y = yp.wrapping_sub(xp).rotate_left(12);
+        self.z = zp.wrapping_sub(yp).rotate_left(44);
+        xp
+    }
+}
+
+impl_portable!(RomuDuoJr { x, y } if |this| [this.x, this.y] != [0; 2]);
+impl_portable!(RomuTrio { x, y, z } if |this| [this.x, this.y, this.z] != [0; 3]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// RomuDuoJr matches the reference implementation for the state {1, 2}
+    fn test_duo_jr_reference_output() {
+        let mut rng = RomuDuoJr { x: 1, y: 2 };
+        let expected = [
+            0x0000000000000001,
+            0xa7067d009e98ae96,
+            0x027a62ba58000000,
+            0xbbf058bed6b89bbd,
+        ];
+        for value in expected {
+            assert_eq!(rng.next_u64(), value);
+        }
+    }
+
+    #[test]
+    /// RomuTrio matches the reference implementation for the state {1, 2, 3}
+    fn test_trio_reference_output() {
+        let mut rng = RomuTrio { x: 1, y: 2, z: 3 };
+        let expected = [
+            0x0000000000000001,
+            0x7a89bb80ede505e1,
+            0xc574b00000000000,
+            0x61cc0dd6fbb3a8b5,
+        ];
+        for value in expected {
+            assert_eq!(rng.next_u64(), value);
+        }
+    }
+}
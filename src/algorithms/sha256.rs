@@ -0,0 +1,181 @@
+#[cfg(all(feature = "zeroize", feature = "fortuna"))]
+use super::wipe;
+
+// Round constants from FIPS 180-4
+#[rustfmt::skip]
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Output length of SHA-256 in bytes
+pub(crate) const DIGEST_LEN: usize = 32;
+
+/// Streaming SHA-256 (FIPS 180-4), used by the DRBG backends, ChaCha rekeying
+/// and the Fortuna accumulator
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Sha256 {
+    state: [u32; 8],
+    buffer: [u8; 64],
+    filled: usize,
+    length: u64,
+}
+
+impl Sha256 {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: INITIAL_STATE,
+            buffer: [0; 64],
+            filled: 0,
+            length: 0,
+        }
+    }
+
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        self.length = self.length.wrapping_add(data.len() as u64);
+        for byte in data {
+            self.buffer[self.filled] = *byte;
+            self.filled += 1;
+            if self.filled == 64 {
+                self.compress();
+            }
+        }
+    }
+
+    pub(crate) fn finalize(mut self) -> [u8; DIGEST_LEN] {
+        let bit_length = self.length.wrapping_mul(8);
+        self.buffer[self.filled] = 0x80;
+        self.filled += 1;
+        if self.filled > 56 {
+            self.buffer[self.filled..].fill(0);
+            self.compress();
+        }
+        self.buffer[self.filled..56].fill(0);
+        self.buffer[56..].copy_from_slice(&bit_length.to_be_bytes());
+        self.compress();
+
+        let mut digest = [0u8; DIGEST_LEN];
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(self.state) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+
+    /// Overwrite the chaining state and buffered input with zeros
+    #[cfg(all(feature = "zeroize", feature = "fortuna"))]
+    pub(crate) fn zeroize(&mut self) {
+        wipe(&mut self.state, [0; 8]);
+        wipe(&mut self.buffer, [0; 64]);
+        wipe(&mut self.filled, 0);
+        wipe(&mut self.length, 0);
+    }
+
+    // Process the full buffered block
+    fn compress(&mut self) {
+        let mut w = [0u32; 64];
+        for (word, bytes) in w.iter_mut().zip(self.buffer.chunks_exact(4)) {
+            *word = u32::from_be_bytes(bytes.try_into().unwrap());
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let temp1 = h
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(K[i])
+                .wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let temp2 = s0.wrapping_add(maj);
+
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(temp1);
+            d = c;
+            c = b;
+            b = a;
+            a = temp1.wrapping_add(temp2);
+        }
+
+        for (word, value) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *word = word.wrapping_add(value);
+        }
+        self.filled = 0;
+    }
+}
+
+/// HMAC-SHA-256 over the concatenation of `parts`
+#[cfg(feature = "drbg")]
+pub(crate) fn hmac_sha256(key: &[u8; DIGEST_LEN], parts: &[&[u8]]) -> [u8; DIGEST_LEN] {
+    let mut block = [0u8; 64];
+    block[..DIGEST_LEN].copy_from_slice(key);
+
+    let mut inner = Sha256::new();
+    inner.update(&block.map(|byte| byte ^ 0x36));
+    for part in parts {
+        inner.update(part);
+    }
+
+    let mut outer = Sha256::new();
+    outer.update(&block.map(|byte| byte ^ 0x5c));
+    outer.update(&inner.finalize());
+    outer.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::decode_hex;
+
+    fn digest(data: &[u8]) -> [u8; DIGEST_LEN] {
+        let mut sha = Sha256::new();
+        sha.update(data);
+        sha.finalize()
+    }
+
+    #[test]
+    /// Digests match the FIPS 180-4 examples
+    fn test_fips180_vectors() {
+        assert_eq!(
+            digest(b"abc"),
+            decode_hex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+        assert_eq!(
+            digest(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            decode_hex("248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1")
+        );
+    }
+
+    #[cfg(feature = "drbg")]
+    #[test]
+    /// HMAC matches RFC 4231 test case 2 with the key zero-padded to 32 bytes
+    fn test_hmac_rfc4231() {
+        let mut key = [0u8; DIGEST_LEN];
+        key[..4].copy_from_slice(b"Jefe");
+        assert_eq!(
+            hmac_sha256(&key, &[b"what do ya want ", b"for nothing?"]),
+            decode_hex("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+    }
+}
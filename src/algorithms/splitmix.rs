@@ -0,0 +1,98 @@
+use super::portable::impl_portable;
+
+// Weyl sequence increment, the odd integer closest to 2^64 / golden ratio
+pub(crate) const GAMMA: u64 = 0x9e3779b97f4a7c15;
+
+/// SplitMix64 by Sebastiano Vigna, after Guy Steele's SplittableRandom
+///
+/// A Weyl sequence passed through a strong bit mixer. Besides being a generator
+/// in its own right it is used to expand a `u64` seed into larger states, so
+/// nearby seeds such as 0 and 1 still give unrelated streams.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub(crate) fn seed_word(&self) -> u64 {
+        self.state
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(GAMMA);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Step back one output, which is skipping ahead a full period of 2^64 less one
+    pub(crate) fn previous(&mut self) {
+        self.advance(u64::MAX);
+    }
+
+    /// Skip `delta` outputs in constant time, the state being a Weyl sequence
+    pub(crate) fn advance(&mut self, delta: u64) {
+        self.state = self.state.wrapping_add(GAMMA.wrapping_mul(delta));
+    }
+}
+
+impl_portable!(SplitMix64 { state });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Output matches the reference implementation seeded with 0
+    fn test_reference_output() {
+        let mut rng = SplitMix64::new(0);
+        let expected = [
+            0xe220a8397b1dcdaf,
+            0x6e789e6aa1b965f4,
+            0x06c45d188009454f,
+            0xf88bb8a8724c81ec,
+        ];
+        for value in expected {
+            assert_eq!(rng.next_u64(), value);
+        }
+    }
+
+    #[test]
+    /// Adjacent seeds do not produce shifted copies of each other
+    fn test_adjacent_seeds_uncorrelated() {
+        let mut zero = SplitMix64::new(0);
+        let mut one = SplitMix64::new(1);
+        let first_zero = zero.next_u64();
+        let first_one = one.next_u64();
+        assert_eq!(first_one, 0x910a2dec89025cc1);
+        assert_ne!(first_one, zero.next_u64());
+        assert_ne!(first_zero, one.next_u64());
+    }
+
+    #[test]
+    /// Advancing matches stepping one output at a time
+    fn test_advance() {
+        let mut stepped = SplitMix64::new(3);
+        let mut advanced = stepped.clone();
+        for _ in 0..1000 {
+            stepped.next_u64();
+        }
+        advanced.advance(1000);
+        assert_eq!(advanced, stepped);
+    }
+
+    #[test]
+    /// Stepping back undoes one output
+    fn test_previous() {
+        let mut rng = SplitMix64::new(3);
+        let start = rng.clone();
+        rng.next_u64();
+        rng.previous();
+        assert_eq!(rng, start);
+    }
+}
@@ -0,0 +1,129 @@
+use super::portable::impl_portable;
+
+// Rotation constants and key schedule parity from the Threefish specification
+const ROTATIONS: [u32; 8] = [16, 42, 12, 31, 16, 32, 24, 21];
+const PARITY: u64 = 0x1bd11bdaa9fc1a22;
+
+/// Threefry2x64-20 counter-based generator by Salmon et al. (Random123)
+///
+/// Each 128-bit counter is encrypted with a reduced-round Threefish under a key
+/// derived from the seed, giving one block of two `u64` outputs. Like Philox, any
+/// output can be computed directly from its index.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Threefry2x64 {
+    key: [u64; 2],
+    counter: u128,
+    block: [u64; 2],
+    index: usize,
+}
+
+impl Threefry2x64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self::with_stream(seed, 0)
+    }
+
+    /// The stream fills the second key word
+    pub(crate) fn with_stream(seed: u64, stream: u64) -> Self {
+        Self {
+            key: [seed, stream],
+            counter: 0,
+            block: [0; 2],
+            index: 2,
+        }
+    }
+
+    pub(crate) fn seed_word(&self) -> u64 {
+        self.key[0] ^ self.key[1]
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        if self.index >= 2 {
+            self.refill();
+        }
+        let value = self.block[self.index];
+        self.index += 1;
+        value
+    }
+
+    /// Position the generator so the next output is the one at `index`
+    pub(crate) fn seek(&mut self, index: u64) {
+        self.counter = (index / 2) as u128;
+        self.refill();
+        self.index = (index % 2) as usize;
+    }
+
+    // Encrypt the current counter into the output block, then bump the counter
+    fn refill(&mut self) {
+        self.block = threefry2x64([self.counter as u64, (self.counter >> 64) as u64], self.key);
+        self.counter = self.counter.wrapping_add(1);
+        self.index = 0;
+    }
+}
+
+// Threefry2x64 block function with 20 rounds
+fn threefry2x64(ctr: [u64; 2], key: [u64; 2]) -> [u64; 2] {
+    let ks = [key[0], key[1], PARITY ^ key[0] ^ key[1]];
+    let mut x0 = ctr[0].wrapping_add(ks[0]);
+    let mut x1 = ctr[1].wrapping_add(ks[1]);
+    for round in 0..20 {
+        x0 = x0.wrapping_add(x1);
+        x1 = x1.rotate_left(ROTATIONS[round % 8]) ^ x0;
+        if round % 4 == 3 {
+            let injection = (round + 1) / 4;
+            x0 = x0.wrapping_add(ks[injection % 3]);
+            x1 = x1
+                .wrapping_add(ks[(injection + 1) % 3])
+                .wrapping_add(injection as u64);
+        }
+    }
+    [x0, x1]
+}
+
+impl_portable!(Threefry2x64 { key, counter, block, index } if |this| this.index <= 2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// The block function matches the Random123 known-answer vectors
+    fn test_known_answers() {
+        assert_eq!(
+            threefry2x64([0; 2], [0; 2]),
+            [0xc2b6e3a8c2c69865, 0x6f81ed42f350084d]
+        );
+        assert_eq!(
+            threefry2x64([u64::MAX; 2], [u64::MAX; 2]),
+            [0xe02cb7c4d95d277a, 0xd06633d0893b8b68]
+        );
+        assert_eq!(
+            threefry2x64(
+                [0x243f6a8885a308d3, 0x13198a2e03707344],
+                [0xa4093822299f31d0, 0x082efa98ec4e6c89]
+            ),
+            [0x263c7d30bb0f0af1, 0x56be8361d3311526]
+        );
+    }
+
+    #[test]
+    /// Outputs are the encrypted counters 0, 1, ... in order
+    fn test_output_order() {
+        let mut rng = Threefry2x64::new(42);
+        assert_eq!(rng.next_u64(), 0x3873f40c23d69344);
+        assert_eq!(rng.next_u64(), 0x33b159ac327fe647);
+        assert_eq!(rng.next_u64(), 0x17d339c909e53856);
+        assert_eq!(rng.next_u64(), 0xa02c4831217721d7);
+    }
+
+    #[test]
+    /// seek jumps straight to the requested output
+    fn test_seek() {
+        let mut sequential = Threefry2x64::new(7);
+        let values: [u64; 9] = core::array::from_fn(|_| sequential.next_u64());
+        let mut direct = Threefry2x64::new(7);
+        for index in [8, 3, 0, 5] {
+            direct.seek(index);
+            assert_eq!(direct.next_u64(), values[index as usize]);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use super::expand_seed;
+use super::portable::impl_portable;
+
+const JUMP: [u64; 2] = [0xdf900294d8f554a5, 0x170865df4b3201fc];
+const LONG_JUMP: [u64; 2] = [0xd2a98b26625eee7b, 0xdddf9b1090aa7ac1];
+
+/// xoroshiro128+ by David Blackman and Sebastiano Vigna
+///
+/// Keeps 128 bits of state, seeded by expanding the `u64` seed with SplitMix64.
+/// The lowest bits of the output are of lower quality than the upper ones.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Xoroshiro128Plus {
+    s: [u64; 2],
+}
+
+impl Xoroshiro128Plus {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self {
+            s: expand_seed(seed),
+        }
+    }
+
+    pub(crate) fn seed_word(&self) -> u64 {
+        self.s[0] ^ self.s[1]
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        let s0 = self.s[0];
+        let mut s1 = self.s[1];
+        let result = s0.wrapping_add(s1);
+
+        s1 ^= s0;
+        self.s[0] = s0.rotate_left(24) ^ s1 ^ (s1 << 16);
+        self.s[1] = s1.rotate_left(37);
+
+        result
+    }
+
+    /// Advance the state by 2^64 outputs
+    pub(crate) fn jump(&mut self) {
+        self.apply_jump(&JUMP);
+    }
+
+    /// Advance the state by 2^96 outputs
+    pub(crate) fn long_jump(&mut self) {
+        self.apply_jump(&LONG_JUMP);
+    }
+
+    // Evaluate the jump polynomial at the current state
+    fn apply_jump(&mut self, polynomial: &[u64; 2]) {
+        let mut jumped = [0u64; 2];
+        for word in polynomial {
+            for bit in 0..64 {
+                if word & (1 << bit) != 0 {
+                    jumped[0] ^= self.s[0];
+                    jumped[1] ^= self.s[1];
+                }
+                self.next_u64();
+            }
+        }
+        self.s = jumped;
+    }
+}
+
+impl_portable!(Xoroshiro128Plus { s } if |this| this.s != [0; 2]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Output matches the reference implementation for the state {1, 2}
+    fn test_reference_output() {
+        let mut rng = Xoroshiro128Plus { s: [1, 2] };
+        let expected = [
+            0x0000000000000003,
+            0x0000006001030003,
+            0x20c102c302000c03,
+            0x810180670d23ad61,
+            0x26d13a4941333a42,
+            0x538a501c02f58b2e,
+        ];
+        for value in expected {
+            assert_eq!(rng.next_u64(), value);
+        }
+    }
+
+    #[test]
+    /// Seeding expands the u64 seed with SplitMix64
+    fn test_seed_expansion() {
+        let mut rng = Xoroshiro128Plus::new(42);
+        assert_eq!(rng.next_u64(), 0xe6c71559e2525f98);
+        assert_eq!(rng.next_u64(), 0x13b69ac93ec06b57);
+        assert_eq!(rng.next_u64(), 0x879006cb74f40d36);
+    }
+
+    #[test]
+    /// Jumps match 2^64 and 2^96 steps of the state transition
+    fn test_jump() {
+        let mut rng = Xoroshiro128Plus { s: [1, 2] };
+        rng.jump();
+        assert_eq!(rng.s, [0x66fbd4be1df0a7b5, 0x830c3ddbb4aa3172]);
+        let mut rng = Xoroshiro128Plus { s: [1, 2] };
+        rng.long_jump();
+        assert_eq!(rng.s, [0x3ce44494d47d323a, 0x2aa25ca8d61de643]);
+    }
+}
@@ -0,0 +1,65 @@
+use super::expand_seed;
+use super::portable::impl_portable;
+
+/// Xorshift64* by Sebastiano Vigna
+///
+/// A single 64-bit word of xorshift state with a multiplicative output scrambler.
+/// The state must never be zero, so the seed is mixed with SplitMix64 first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Xorshift64Star {
+    state: u64,
+}
+
+impl Xorshift64Star {
+    pub(crate) fn new(seed: u64) -> Self {
+        let [state] = expand_seed(seed);
+        Self {
+            state: if state == 0 {
+                0x9e3779b97f4a7c15
+            } else {
+                state
+            },
+        }
+    }
+
+    pub(crate) fn seed_word(&self) -> u64 {
+        self.state
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545f4914f6cdd1d)
+    }
+}
+
+impl_portable!(Xorshift64Star { state } if |this| this.state != 0);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Output matches the reference implementation for the state 1
+    fn test_reference_output() {
+        let mut rng = Xorshift64Star { state: 1 };
+        let expected = [
+            0x47e4ce4b896cdd1d,
+            0xabcfa6a8e079651d,
+            0xb9d10d8feb731f57,
+            0x4db418a0bb1b019d,
+        ];
+        for value in expected {
+            assert_eq!(rng.next_u64(), value);
+        }
+    }
+
+    #[test]
+    /// A zero seed still yields a non-zero state
+    fn test_zero_seed() {
+        let mut rng = Xorshift64Star::new(0);
+        assert_ne!(rng.state, 0);
+        assert_ne!(rng.next_u64(), 0);
+    }
+}
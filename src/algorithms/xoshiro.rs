@@ -0,0 +1,158 @@
+use super::expand_seed;
+use super::portable::impl_portable;
+
+const JUMP: [u64; 4] = [
+    0x180ec6d33cfd0aba,
+    0xd5a61266f0c9392c,
+    0xa9582618e03fc9aa,
+    0x39abdc4529b1661c,
+];
+const LONG_JUMP: [u64; 4] = [
+    0x76e15d3efefdcbbf,
+    0xc5004e441c522fb3,
+    0x77710069854ee241,
+    0x39109bb02acbe635,
+];
+
+/// xoshiro256++ by David Blackman and Sebastiano Vigna
+///
+/// Keeps 256 bits of state. The 64-bit seed is expanded with SplitMix64, as
+/// recommended by the authors, which also guarantees the state is never all zeros.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Xoshiro256PlusPlus {
+    s: [u64; 4],
+}
+
+impl Xoshiro256PlusPlus {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self {
+            s: expand_seed(seed),
+        }
+    }
+
+    /// Use the seed bytes as the four state words, little-endian
+    ///
+    /// The all-zero state is a fixed point, so an all-zero seed falls back to
+    /// expanding the seed 0.
+    pub(crate) fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        let (words, _) = seed.as_chunks::<8>();
+        let s = core::array::from_fn(|i| u64::from_le_bytes(words[i]));
+        if s == [0; 4] {
+            Self::new(0)
+        } else {
+            Self { s }
+        }
+    }
+
+    /// The four state words
+    #[cfg(feature = "simd")]
+    pub(crate) fn words(&self) -> [u64; 4] {
+        self.s
+    }
+
+    pub(crate) fn seed_word(&self) -> u64 {
+        self.s[0] ^ self.s[1] ^ self.s[2] ^ self.s[3]
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        let s = &mut self.s;
+        let result = s[0].wrapping_add(s[3]).rotate_left(23).wrapping_add(s[0]);
+        let t = s[1] << 17;
+
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+
+        result
+    }
+
+    /// Advance the state by 2^128 outputs
+    pub(crate) fn jump(&mut self) {
+        self.apply_jump(&JUMP);
+    }
+
+    /// Advance the state by 2^192 outputs
+    pub(crate) fn long_jump(&mut self) {
+        self.apply_jump(&LONG_JUMP);
+    }
+
+    // Evaluate the jump polynomial at the current state
+    fn apply_jump(&mut self, polynomial: &[u64; 4]) {
+        let mut jumped = [0u64; 4];
+        for word in polynomial {
+            for bit in 0..64 {
+                if word & (1 << bit) != 0 {
+                    for (j, s) in jumped.iter_mut().zip(&self.s) {
+                        *j ^= s;
+                    }
+                }
+                self.next_u64();
+            }
+        }
+        self.s = jumped;
+    }
+}
+
+impl_portable!(Xoshiro256PlusPlus { s } if |this| this.s != [0; 4]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Output matches the reference implementation for the state {1, 2, 3, 4}
+    fn test_reference_output() {
+        let mut rng = Xoshiro256PlusPlus { s: [1, 2, 3, 4] };
+        let expected = [
+            0x0000000002800001,
+            0x0000000003800067,
+            0x000cc00003800067,
+            0x000cc201994400b2,
+            0x8012a2019ac433cd,
+            0x8a69978acdee33ba,
+        ];
+        for value in expected {
+            assert_eq!(rng.next_u64(), value);
+        }
+    }
+
+    #[test]
+    /// Seeding expands the u64 seed with SplitMix64
+    fn test_seed_expansion() {
+        let mut rng = Xoshiro256PlusPlus::new(42);
+        assert_eq!(rng.next_u64(), 0xd0764d4f4476689f);
+        assert_eq!(rng.next_u64(), 0x519e4174576f3791);
+        assert_eq!(rng.next_u64(), 0xfbe07cfb0c24ed8c);
+    }
+
+    #[test]
+    /// Jumps match 2^128 and 2^192 steps of the state transition
+    fn test_jump() {
+        let mut rng = Xoshiro256PlusPlus { s: [1, 2, 3, 4] };
+        rng.jump();
+        assert_eq!(
+            rng.s,
+            [
+                0x8c7a153956b5f3d1,
+                0x701f1a713401d85e,
+                0x6527f66a65469085,
+                0x8386b786c4408050
+            ]
+        );
+        let mut rng = Xoshiro256PlusPlus { s: [1, 2, 3, 4] };
+        rng.long_jump();
+        assert_eq!(
+            rng.s,
+            [
+                0x096a8eb71295a400,
+                0xdbf84991e50f4516,
+                0x534ee745810d2a0e,
+                0x31655ca1a2215bf1
+            ]
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use super::expand_seed;
+use super::portable::impl_portable;
+
+/// xoshiro128++ by David Blackman and Sebastiano Vigna
+///
+/// Keeps 128 bits of state in four `u32` words and needs no multiply, which
+/// makes it the native choice for 32-bit microcontrollers. The 64-bit seed is
+/// expanded with SplitMix64. Each `u64` output joins two `u32` outputs, the
+/// first in the low half.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Xoshiro128PlusPlus {
+    s: [u32; 4],
+}
+
+impl Xoshiro128PlusPlus {
+    pub(crate) fn new(seed: u64) -> Self {
+        let [a, b] = expand_seed(seed);
+        Self {
+            s: [a as u32, (a >> 32) as u32, b as u32, (b >> 32) as u32],
+        }
+    }
+
+    pub(crate) fn seed_word(&self) -> u64 {
+        ((self.s[1] ^ self.s[3]) as u64) << 32 | (self.s[0] ^ self.s[2]) as u64
+    }
+
+    pub(crate) fn next_u32(&mut self) -> u32 {
+        let s = &mut self.s;
+        let result = s[0].wrapping_add(s[3]).rotate_left(7).wrapping_add(s[0]);
+        let t = s[1] << 9;
+
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(11);
+
+        result
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        let low = self.next_u32() as u64;
+        let high = self.next_u32() as u64;
+        (high << 32) | low
+    }
+}
+
+impl_portable!(Xoshiro128PlusPlus { s } if |this| this.s != [0; 4]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Output matches the reference implementation for the state {1, 2, 3, 4}
+    fn test_reference_output() {
+        let mut rng = Xoshiro128PlusPlus { s: [1, 2, 3, 4] };
+        let expected = [
+            0x00000281, 0x00180387, 0xc0183387, 0xd1ae3b02, 0x31e2310a, 0xfd275ab0,
+        ];
+        for value in expected {
+            assert_eq!(rng.next_u32(), value);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! A generator shared between threads without locking, see [`AtomicRng`].
+
+use crate::algorithms::splitmix::GAMMA;
+use crate::{RngAlgorithm, SplitMix64};
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// A SplitMix64 generator that many threads can draw from at once
+///
+/// The state is a Weyl sequence, so each output claims its step with one atomic
+/// add and no thread ever waits or retries. Every thread sees a different part
+/// of the one sequence, which is the same as [`SplitMix64`] from the same seed.
+/// `&AtomicRng` implements [`RngAlgorithm`], so [`crate::RNG::from_generator`]
+/// gives a handle with all the `RNG` helpers. Only available on targets with
+/// 64-bit atomics.
+///
+/// # Example
+/// ```rust
+/// use simple_rng::{AtomicRng, RNG};
+///
+/// static JITTER: AtomicRng = AtomicRng::new(42);
+///
+/// std::thread::scope(|scope| {
+///     for _ in 0..4 {
+///         scope.spawn(|| {
+///             let delay_ms = RNG::from_generator(&JITTER).gen_range(0..250u64);
+///             println!("{}", delay_ms);
+///         });
+///     }
+/// });
+/// ```
+#[derive(Debug)]
+pub struct AtomicRng {
+    state: AtomicU64,
+}
+
+impl AtomicRng {
+    /// Create a shared generator, also in `static` items
+    pub const fn new(seed: u64) -> Self {
+        Self {
+            state: AtomicU64::new(seed),
+        }
+    }
+
+    /// Next random u64, from any thread
+    pub fn next(&self) -> u64 {
+        let state = self.state.fetch_add(GAMMA, Ordering::Relaxed);
+        SplitMix64::new(state).next_u64()
+    }
+}
+
+impl RngAlgorithm for &AtomicRng {
+    fn next_u64(&mut self) -> u64 {
+        AtomicRng::next(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// The shared sequence matches SplitMix64
+    fn test_matches_splitmix() {
+        let shared = AtomicRng::new(5);
+        let mut plain = SplitMix64::new(5);
+        for _ in 0..10 {
+            assert_eq!(shared.next(), plain.next_u64());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    /// Threads drawing together never get the same output twice
+    fn test_threads_share_sequence() {
+        let shared = AtomicRng::new(9);
+        let mut values: std::vec::Vec<u64> = std::thread::scope(|scope| {
+            let workers: std::vec::Vec<_> = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        (0..1000)
+                            .map(|_| shared.next())
+                            .collect::<std::vec::Vec<_>>()
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().unwrap())
+                .collect()
+        });
+        values.sort();
+        values.dedup();
+        assert_eq!(values.len(), 4000);
+    }
+}
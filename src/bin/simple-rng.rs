@@ -0,0 +1,150 @@
+//! Command-line front end for shell scripts (requires `cli` feature).
+
+use simple_rng::RNG;
+use std::io::{self, BufRead, Write};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: simple-rng [--seed N] <command> [options]
+
+Commands:
+  int [--min N] [--max N]   Random integer in [min, max], 1 to 100 by default
+  float                     Random float in [0.0, 1.0)
+  bytes [--hex] N           N random bytes, raw or as hex
+  choice ITEM...            One of the items
+  shuffle [ITEM...]         The items, or the lines of stdin, in random order
+  uuid                      Random version 4 UUID
+
+Without --seed the generator is seeded from OS entropy.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("simple-rng: {message}\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(mut args: &[String]) -> Result<(), String> {
+    let mut rng = match args {
+        [flag, seed, rest @ ..] if flag == "--seed" => {
+            args = rest;
+            RNG::new(parse(seed)?)
+        }
+        _ => RNG::from_entropy(),
+    };
+    let Some((command, args)) = args.split_first() else {
+        return Err("missing command".into());
+    };
+    let mut out = io::stdout().lock();
+    let written = match command.as_str() {
+        "int" => {
+            let min = option(args, "--min")?.unwrap_or(1);
+            let max = option(args, "--max")?.unwrap_or(100);
+            let value: i64 = rng
+                .try_gen_range(min..=max)
+                .map_err(|error| error.to_string())?;
+            writeln!(out, "{value}")
+        }
+        "float" => writeln!(out, "{}", rng.gen_float()),
+        "bytes" => {
+            let (hex, len) = match args {
+                [flag, len] if flag == "--hex" => (true, len),
+                [len] => (false, len),
+                _ => return Err("bytes takes a length".into()),
+            };
+            let mut bytes = vec![0u8; parse(len)?];
+            rng.fill_bytes(&mut bytes);
+            if hex {
+                writeln!(out, "{}", to_hex(&bytes))
+            } else {
+                out.write_all(&bytes)
+            }
+        }
+        "choice" => match rng.pick_random(args) {
+            Some(item) => writeln!(out, "{item}"),
+            None => return Err("choice needs at least one item".into()),
+        },
+        "shuffle" => {
+            let mut items = args.to_vec();
+            if items.is_empty() {
+                items = io::stdin()
+                    .lock()
+                    .lines()
+                    .collect::<Result<_, _>>()
+                    .map_err(|error| error.to_string())?;
+            }
+            rng.shuffle(&mut items);
+            items.iter().try_for_each(|item| writeln!(out, "{item}"))
+        }
+        "uuid" => writeln!(out, "{}", uuid_v4(rng.gen_array())),
+        "--help" | "-h" | "help" => writeln!(out, "{USAGE}"),
+        other => return Err(format!("unknown command `{other}`")),
+    };
+    written.map_err(|error| error.to_string())
+}
+
+fn parse<T: std::str::FromStr>(text: &str) -> Result<T, String> {
+    text.parse().map_err(|_| format!("invalid number `{text}`"))
+}
+
+// The value after `name`, if it is given
+fn option(args: &[String], name: &str) -> Result<Option<i64>, String> {
+    match args.iter().position(|arg| arg == name) {
+        Some(index) => match args.get(index + 1) {
+            Some(value) => parse(value).map(Some),
+            None => Err(format!("{name} needs a value")),
+        },
+        None => Ok(None),
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+// Set the version and variant bits of RFC 9562 and format as 8-4-4-4-12
+fn uuid_v4(mut bytes: [u8; 16]) -> String {
+    bytes[6] = (bytes[6] & 0x0F) | 0x40;
+    bytes[8] = (bytes[8] & 0x3F) | 0x80;
+    let hex = to_hex(&bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// UUIDs carry the version 4 and RFC variant bits
+    fn test_uuid_v4() {
+        assert_eq!(uuid_v4([0; 16]), "00000000-0000-4000-8000-000000000000");
+        assert_eq!(uuid_v4([0xFF; 16]), "ffffffff-ffff-4fff-bfff-ffffffffffff");
+    }
+
+    fn strings(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    /// Options are read by name and bad input is an error
+    fn test_options() {
+        let args = strings(&["--max", "6", "--min", "-2"]);
+        assert_eq!(option(&args, "--min"), Ok(Some(-2)));
+        assert_eq!(option(&args, "--max"), Ok(Some(6)));
+        assert_eq!(option(&args, "--step"), Ok(None));
+        assert!(run(&strings(&["--seed", "x", "float"])).is_err());
+        assert!(run(&strings(&["int", "--min", "5", "--max", "1"])).is_err());
+        assert!(run(&strings(&["choice"])).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+//! Block buffering for any generator, see [`Buffered`].
+
+use crate::{RngAlgorithm, SeedableAlgorithm};
+
+/// Number of outputs generated per refill
+const BUFFER_LEN: usize = 256;
+
+/// Wraps a generator and serves outputs from a buffer of 256 words
+///
+/// The buffer is refilled with a single [`RngAlgorithm::fill_u64`] call, so
+/// block-based generators such as ChaCha write whole blocks at once and each
+/// `next` is just a read from the buffer. The sequence is the same as the
+/// wrapped generator's.
+///
+/// # Example
+/// ```rust
+/// use simple_rng::{Buffered, RNG, Xoshiro256PlusPlus};
+/// let mut rng = RNG::<Buffered<Xoshiro256PlusPlus>>::from_seed(42);
+/// let value = rng.next();
+/// println!("{}", value);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Buffered<A: RngAlgorithm> {
+    generator: A,
+    buffer: [u64; BUFFER_LEN],
+    index: usize,
+}
+
+impl<A: RngAlgorithm> Buffered<A> {
+    /// Wrap `generator`, generating its first block on the first output
+    pub fn new(generator: A) -> Self {
+        Self {
+            generator,
+            buffer: [0; BUFFER_LEN],
+            index: BUFFER_LEN,
+        }
+    }
+}
+
+impl<A: RngAlgorithm> RngAlgorithm for Buffered<A> {
+    fn next_u64(&mut self) -> u64 {
+        if self.index == BUFFER_LEN {
+            self.generator.fill_u64(&mut self.buffer);
+            self.index = 0;
+        }
+        let value = self.buffer[self.index];
+        self.index += 1;
+        value
+    }
+
+    // Drain the buffer, then let the generator write the rest directly
+    fn fill_u64(&mut self, dest: &mut [u64]) {
+        let buffered = (BUFFER_LEN - self.index).min(dest.len());
+        let (head, rest) = dest.split_at_mut(buffered);
+        head.copy_from_slice(&self.buffer[self.index..self.index + buffered]);
+        self.index += buffered;
+        self.generator.fill_u64(rest);
+    }
+}
+
+impl<A: SeedableAlgorithm> SeedableAlgorithm for Buffered<A> {
+    fn from_seed(seed: u64) -> Self {
+        Self::new(A::from_seed(seed))
+    }
+
+    fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        Self::new(A::from_seed_bytes(seed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Algorithm, RNG};
+
+    #[test]
+    /// Buffering does not change the sequence
+    fn test_same_sequence() {
+        let mut buffered = RNG::from_generator(Buffered::new(
+            RNG::with_algorithm(3, Algorithm::Sfc64).state,
+        ));
+        let mut plain = RNG::with_algorithm(3, Algorithm::Sfc64);
+        for _ in 0..600 {
+            assert_eq!(buffered.next(), plain.next());
+        }
+    }
+
+    #[test]
+    /// Mixing single outputs and fills keeps the order
+    fn test_fill_after_next() {
+        let mut buffered = Buffered::new(RNG::new(8).state);
+        let mut plain = RNG::new(8);
+        buffered.next_u64();
+        plain.next();
+        let mut out = [0u64; 300];
+        buffered.fill_u64(&mut out);
+        for value in out {
+            assert_eq!(value, plain.next());
+        }
+        assert_eq!(buffered.next_u64(), plain.next());
+    }
+}
@@ -0,0 +1,94 @@
+//! Step-by-step RNG configuration, see [`RngBuilder`].
+
+use crate::{Algorithm, RNG};
+
+/// Configures an [`RNG`] one setting at a time, created by [`RNG::builder`]
+///
+/// Settings left out keep their defaults: seed 0, the LCG algorithm and the
+/// algorithm's default stream.
+///
+/// # Example
+/// ```rust
+/// use simple_rng::{Algorithm, RNG};
+/// let mut rng = RNG::builder()
+///     .seed(42)
+///     .algorithm(Algorithm::Philox4x32)
+///     .stream(7)
+///     .build();
+/// let value = rng.next();
+/// println!("{}", value);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RngBuilder {
+    seed: u64,
+    algorithm: Algorithm,
+    stream: Option<u64>,
+}
+
+impl RngBuilder {
+    /// Start from the default settings
+    pub fn new() -> Self {
+        Self {
+            seed: 0,
+            algorithm: Algorithm::Lcg,
+            stream: None,
+        }
+    }
+
+    /// Set the seed
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set the algorithm
+    pub fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Select an independent stream, see [`RNG::with_algorithm_and_stream`]
+    pub fn stream(mut self, stream: u64) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+
+    /// Create the RNG
+    ///
+    /// Panics if a stream was set and the algorithm has no streams.
+    pub fn build(self) -> RNG {
+        match self.stream {
+            Some(stream) => RNG::with_algorithm_and_stream(self.seed, stream, self.algorithm),
+            None => RNG::with_algorithm(self.seed, self.algorithm),
+        }
+    }
+}
+
+impl Default for RngBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// The builder matches the direct constructors
+    fn test_matches_constructors() {
+        let mut built = RNG::builder().seed(5).algorithm(Algorithm::Sfc64).build();
+        let mut direct = RNG::with_algorithm(5, Algorithm::Sfc64);
+        assert_eq!(built.next(), direct.next());
+
+        let mut built = RNG::builder()
+            .algorithm(Algorithm::Philox4x32)
+            .stream(3)
+            .seed(9)
+            .build();
+        let mut direct = RNG::with_algorithm_and_stream(9, 3, Algorithm::Philox4x32);
+        assert_eq!(built.next(), direct.next());
+
+        assert_eq!(RNG::default().next(), RNG::new(0).next());
+    }
+}
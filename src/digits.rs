@@ -0,0 +1,236 @@
+//! Random arbitrary-precision integers as little-endian `u32` digits, see
+//! [`RNG::gen_digits`], [`RNG::gen_digits_below`] and
+//! [`RNG::gen_digits_signed_below`].
+//!
+//! Digits are stored least significant first, the layout of
+//! `num_bigint::BigUint::new` and `BigUint::to_u32_digits`, so results convert
+//! to any big integer type without copying through bytes.
+
+use crate::{RNG, RngAlgorithm, RngError};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+impl<A: RngAlgorithm> RNG<A> {
+    /// Random integer in [0, 2^`bits`), as `bits.div_ceil(32)` little-endian
+    /// `u32` digits (requires `alloc` feature)
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::new(42);
+    /// let key = rng.gen_digits(100);
+    /// assert_eq!(key.len(), 4);
+    /// assert!(key[3] < 1 << 4);
+    /// ```
+    pub fn gen_digits(&mut self, bits: u32) -> Vec<u32> {
+        let mut digits = vec![0; bits.div_ceil(32) as usize];
+        self.fill_digits(&mut digits, bits);
+        digits
+    }
+
+    /// Random integer in [0, `bound`), with `bound` and the result as
+    /// little-endian `u32` digits of the same length (requires `alloc` feature)
+    ///
+    /// Every value below the bound is exactly equally likely: candidates with
+    /// the bound's bit length are drawn until one is below it, which takes
+    /// fewer than two draws on average. Panics if `bound` is zero.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::new(42);
+    /// // 2^64 + 5
+    /// let value = rng.gen_digits_below(&[5, 0, 1]);
+    /// assert!(value[2] == 0 || (value[2] == 1 && value[1] == 0 && value[0] < 5));
+    /// ```
+    pub fn gen_digits_below(&mut self, bound: &[u32]) -> Vec<u32> {
+        let mut digits = vec![0; bound.len()];
+        self.fill_digits_below(&mut digits, bound);
+        digits
+    }
+
+    /// Random integer in (-`bound`, `bound`), as a sign and little-endian
+    /// `u32` magnitude digits of the same length as `bound` (requires `alloc`
+    /// feature)
+    ///
+    /// The sign is `true` for negative values and never set on zero, the
+    /// layout of `num_bigint::BigInt::from_biguint`. Every value in the range,
+    /// zero included, is exactly equally likely. Panics if `bound` is zero.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::new(42);
+    /// let (negative, magnitude) = rng.gen_digits_signed_below(&[1000, 0]);
+    /// assert!(magnitude[0] < 1000 && magnitude[1] == 0);
+    /// assert!(!(negative && magnitude == [0, 0]));
+    /// ```
+    pub fn gen_digits_signed_below(&mut self, bound: &[u32]) -> (bool, Vec<u32>) {
+        if bit_len(bound) == 0 {
+            panic!("{}", RngError::EmptyRange);
+        }
+        // Draw from [0, 2 * bound - 1) and shift down by bound - 1
+        let mut middle = bound.to_vec();
+        middle.push(0);
+        sub_small(&mut middle, 1);
+        let mut limit = middle.clone();
+        add_assign(&mut limit, bound);
+        let mut value = vec![0; limit.len()];
+        self.fill_digits_below(&mut value, &limit);
+
+        let negative = compare(&value, &middle) == Ordering::Less;
+        if negative {
+            sub_assign(&mut middle, &value);
+            value = middle;
+        } else {
+            sub_assign(&mut value, &middle);
+        }
+        value.truncate(bound.len());
+        (negative, value)
+    }
+
+    // Fill `digits` with a value in [0, 2^bits), clearing any digits above
+    pub(crate) fn fill_digits(&mut self, digits: &mut [u32], bits: u32) {
+        let full = (bits / 32) as usize;
+        for (index, digit) in digits.iter_mut().enumerate() {
+            *digit = match index.cmp(&full) {
+                Ordering::Less => self.next_u32(),
+                Ordering::Equal => self.next_u32() & ((1 << (bits % 32)) - 1),
+                Ordering::Greater => 0,
+            };
+        }
+    }
+
+    // Fill `digits`, the same length as `bound`, with a value in [0, bound)
+    pub(crate) fn fill_digits_below(&mut self, digits: &mut [u32], bound: &[u32]) {
+        let bits = bit_len(bound);
+        if bits == 0 {
+            panic!("{}", RngError::EmptyRange);
+        }
+        loop {
+            self.fill_digits(digits, bits);
+            if compare(digits, bound) == Ordering::Less {
+                return;
+            }
+        }
+    }
+}
+
+/// Number of significant bits in `digits`
+pub(crate) fn bit_len(digits: &[u32]) -> u32 {
+    match digits.iter().rposition(|&digit| digit != 0) {
+        Some(top) => top as u32 * 32 + (32 - digits[top].leading_zeros()),
+        None => 0,
+    }
+}
+
+/// Compare two values with the same number of digits
+pub(crate) fn compare(a: &[u32], b: &[u32]) -> Ordering {
+    a.iter().rev().cmp(b.iter().rev())
+}
+
+/// `a -= b`, returning the borrow out of the top digit
+pub(crate) fn sub_assign(a: &mut [u32], b: &[u32]) -> bool {
+    let mut borrow = false;
+    for (digit, &other) in a.iter_mut().zip(b) {
+        let (value, first) = digit.overflowing_sub(other);
+        let (value, second) = value.overflowing_sub(borrow as u32);
+        *digit = value;
+        borrow = first || second;
+    }
+    borrow
+}
+
+pub(crate) fn sub_small(a: &mut [u32], value: u32) {
+    let mut borrow = value;
+    for digit in a {
+        let (next, carry) = digit.overflowing_sub(borrow);
+        *digit = next;
+        borrow = carry as u32;
+    }
+}
+
+/// `a += b` for `b` no longer than `a`, dropping any carry out of the top digit
+fn add_assign(a: &mut [u32], b: &[u32]) {
+    let mut carry = false;
+    for (index, digit) in a.iter_mut().enumerate() {
+        let (value, first) = digit.overflowing_add(b.get(index).copied().unwrap_or(0));
+        let (value, second) = value.overflowing_add(carry as u32);
+        *digit = value;
+        carry = first || second;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Digits above the requested bit length are zero
+    fn test_gen_digits_masks_top() {
+        let mut rng = RNG::new(7);
+        assert!(rng.gen_digits(0).is_empty());
+        for bits in [1, 31, 32, 33, 64, 65, 200] {
+            let digits = rng.gen_digits(bits);
+            assert_eq!(digits.len(), bits.div_ceil(32) as usize);
+            assert!(bit_len(&digits) <= bits);
+        }
+    }
+
+    #[test]
+    /// Values stay below the bound and reach every residue of a small bound
+    fn test_gen_digits_below() {
+        let mut rng = RNG::new(8);
+        let mut seen = [false; 5];
+        for _ in 0..200 {
+            let value = rng.gen_digits_below(&[5, 0]);
+            assert_eq!(value[1], 0);
+            seen[value[0] as usize] = true;
+        }
+        assert_eq!(seen, [true; 5]);
+
+        let bound = [0, 0, 1];
+        for _ in 0..100 {
+            assert_eq!(
+                compare(&rng.gen_digits_below(&bound), &bound),
+                Ordering::Less
+            );
+        }
+    }
+
+    #[test]
+    /// Signed values cover both sides of zero evenly, with no negative zero
+    fn test_gen_digits_signed_below() {
+        let mut rng = RNG::new(9);
+        let mut counts = [0u32; 5];
+        for _ in 0..5000 {
+            let (negative, magnitude) = rng.gen_digits_signed_below(&[3]);
+            assert!(magnitude[0] < 3);
+            assert!(!(negative && magnitude[0] == 0));
+            let value = if negative {
+                -(magnitude[0] as i32)
+            } else {
+                magnitude[0] as i32
+            };
+            counts[(value + 2) as usize] += 1;
+        }
+        assert!(
+            counts.iter().all(|&count| (850..1150).contains(&count)),
+            "{counts:?}"
+        );
+
+        let bound = [0, 1];
+        for _ in 0..100 {
+            let (_, magnitude) = rng.gen_digits_signed_below(&bound);
+            assert_eq!(compare(&magnitude, &bound), Ordering::Less);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot sample empty range")]
+    /// A zero bound has no values below it
+    fn test_gen_digits_below_zero() {
+        RNG::new(0).gen_digits_below(&[0, 0]);
+    }
+}
@@ -0,0 +1,432 @@
+//! Seeding from entropy sources, see [`EntropySource`].
+//!
+//! The OS interfaces behind [`OsEntropy`], `RNG::from_os_entropy` and
+//! `RNG::from_entropy` are called directly instead of through `std`, so they
+//! also work in `no_std` builds for targets that have an operating system.
+
+use crate::{RNG, RngError, algorithms};
+
+/// Consecutive stuck timer readings after which a jitter source is rejected
+const MAX_STUCK: u32 = 64;
+/// Bytes of scratch memory walked between timer readings
+const NOISE_LEN: usize = 256;
+
+/// A source of seed bytes, such as a microcontroller's hardware TRNG
+///
+/// Implement it for your HAL's TRNG peripheral, or pass a closure, to seed an
+/// [`RNG`] with [`RNG::from_source`] without this crate knowing about the
+/// hardware.
+///
+/// # Example
+/// ```rust
+/// use simple_rng::{EntropySource, RNG, SeedableAlgorithm, Xoshiro256PlusPlus};
+///
+/// // Stands in for a HAL's TRNG peripheral
+/// struct Trng(u8);
+///
+/// impl EntropySource for Trng {
+///     fn fill(&mut self, dest: &mut [u8]) {
+///         for byte in dest {
+///             self.0 = self.0.wrapping_mul(31).wrapping_add(7);
+///             *byte = self.0;
+///         }
+///     }
+/// }
+///
+/// let mut trng = Trng(1);
+/// let mut rng = RNG::from_source(&mut trng);
+///
+/// let mut seed = [0u8; 32];
+/// trng.fill(&mut seed);
+/// let mut typed = RNG::<Xoshiro256PlusPlus>::from_seed_bytes(seed);
+/// println!("{} {}", rng.next(), typed.next());
+/// ```
+pub trait EntropySource {
+    /// Fill `dest` with entropy
+    fn fill(&mut self, dest: &mut [u8]);
+}
+
+impl<F: FnMut(&mut [u8])> EntropySource for F {
+    fn fill(&mut self, dest: &mut [u8]) {
+        self(dest)
+    }
+}
+
+/// Collects entropy from the timing jitter of the CPU, for bare-metal targets
+/// with no hardware TRNG
+///
+/// `counter` reads a fast cycle counter, such as the Cortex-M DWT cycle count.
+/// Between readings the collector walks a small scratch buffer, so cache,
+/// pipeline and bus timing vary from one reading to the next. A reading counts
+/// only when its delta and its change in delta from the last reading are both
+/// non-zero. Each 64-bit output word absorbs `64 * oversampling` of them, so
+/// every bit of output is backed by `oversampling` readings, 2 by default.
+/// Readings are mixed through SplitMix64.
+///
+/// # Example
+/// ```rust
+/// use simple_rng::{JitterEntropy, RNG};
+///
+/// // Stands in for a hardware cycle counter
+/// let mut cycles = 0u64;
+/// let mut noise = 1u64;
+/// let counter = move || {
+///     noise ^= noise << 13;
+///     noise ^= noise >> 7;
+///     noise ^= noise << 17;
+///     cycles += 90 + noise % 16;
+///     cycles
+/// };
+///
+/// let mut jitter = JitterEntropy::new(counter).expect("timer too coarse");
+/// let mut rng = RNG::from_source(&mut jitter);
+/// println!("{}", rng.next());
+/// ```
+pub struct JitterEntropy<F: FnMut() -> u64> {
+    counter: F,
+    last: u64,
+    last_delta: u64,
+    pool: u64,
+    oversampling: u32,
+    noise: [u8; NOISE_LEN],
+}
+
+impl<F: FnMut() -> u64> JitterEntropy<F> {
+    /// Wrap `counter`, checking that it shows enough jitter to use
+    ///
+    /// Returns [`RngError::EntropyUnavailable`] if the counter gets stuck, for
+    /// example because it does not run or ticks too slowly.
+    pub fn new(counter: F) -> Result<Self, RngError> {
+        let mut jitter = Self {
+            counter,
+            last: 0,
+            last_delta: 0,
+            pool: 0,
+            oversampling: 2,
+            noise: [0; NOISE_LEN],
+        };
+        jitter.last = (jitter.counter)();
+        // Warm up, and make sure the counter is usable at all
+        for _ in 0..64 {
+            jitter.sample()?;
+        }
+        Ok(jitter)
+    }
+
+    /// Absorb `64 * oversampling` readings per output word instead of 128
+    ///
+    /// Raise it when the counter's jitter is poor. Panics if `oversampling` is zero.
+    pub fn oversampling(mut self, oversampling: u32) -> Self {
+        if oversampling == 0 {
+            panic!("oversampling must be greater than zero")
+        }
+        self.oversampling = oversampling;
+        self
+    }
+
+    /// Fill `dest` with entropy, or fail if the counter gets stuck
+    pub fn try_fill(&mut self, dest: &mut [u8]) -> Result<(), RngError> {
+        for chunk in dest.chunks_mut(8) {
+            for _ in 0..64 * self.oversampling {
+                self.sample()?;
+            }
+            chunk.copy_from_slice(&self.pool.to_le_bytes()[..chunk.len()]);
+        }
+        Ok(())
+    }
+
+    // Mix the next usable reading into the pool
+    fn sample(&mut self) -> Result<(), RngError> {
+        for _ in 0..MAX_STUCK {
+            self.walk_noise();
+            let now = (self.counter)();
+            let delta = now.wrapping_sub(self.last);
+            let change = delta.wrapping_sub(self.last_delta);
+            self.last = now;
+            self.last_delta = delta;
+            if delta != 0 && change != 0 {
+                let [mixed] = algorithms::expand_seed(self.pool ^ delta);
+                self.pool = mixed;
+                return Ok(());
+            }
+        }
+        Err(RngError::EntropyUnavailable)
+    }
+
+    // Touch scratch memory at pool-dependent positions, so the time taken
+    // varies with cache state
+    fn walk_noise(&mut self) {
+        let mut index = self.pool as usize;
+        for _ in 0..16 {
+            index = index.wrapping_mul(31).wrapping_add(17) % NOISE_LEN;
+            let byte = core::hint::black_box(&mut self.noise[index]);
+            *byte = byte.wrapping_add(1);
+        }
+    }
+}
+
+/// Panics if the counter gets stuck, use [`JitterEntropy::try_fill`] to handle that
+impl<F: FnMut() -> u64> EntropySource for JitterEntropy<F> {
+    fn fill(&mut self, dest: &mut [u8]) {
+        if let Err(error) = self.try_fill(dest) {
+            panic!("{error}")
+        }
+    }
+}
+
+/// The operating system's CSPRNG as an [`EntropySource`]
+///
+/// Panics from [`EntropySource::fill`] if no entropy can be read. Only
+/// available with the `std` or `getrandom` feature.
+#[cfg(any(feature = "std", feature = "getrandom"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct OsEntropy;
+
+#[cfg(any(feature = "std", feature = "getrandom"))]
+impl EntropySource for OsEntropy {
+    fn fill(&mut self, dest: &mut [u8]) {
+        if !fill_os_entropy(dest) {
+            panic!("{}", RngError::EntropyUnavailable)
+        }
+    }
+}
+
+/// Fill `buf` from the operating system's CSPRNG, returning `false` on failure
+/// or on targets without a supported interface
+#[cfg(any(feature = "std", feature = "getrandom"))]
+pub(crate) fn fill_os_entropy(buf: &mut [u8]) -> bool {
+    // Every interface below accepts at least 256 bytes per call
+    buf.chunks_mut(256).all(os::fill)
+}
+
+#[cfg(all(
+    any(feature = "std", feature = "getrandom"),
+    any(target_os = "linux", target_os = "android")
+))]
+mod os {
+    unsafe extern "C" {
+        fn getrandom(buf: *mut u8, buflen: usize, flags: u32) -> isize;
+    }
+
+    // Requests of up to 256 bytes are never cut short once the pool is ready
+    pub(super) fn fill(chunk: &mut [u8]) -> bool {
+        // SAFETY: `chunk` is valid for writes of its length
+        let written = unsafe { getrandom(chunk.as_mut_ptr(), chunk.len(), 0) };
+        written == chunk.len() as isize
+    }
+}
+
+#[cfg(all(
+    any(feature = "std", feature = "getrandom"),
+    any(
+        target_vendor = "apple",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "dragonfly"
+    )
+))]
+mod os {
+    unsafe extern "C" {
+        fn getentropy(buf: *mut u8, buflen: usize) -> i32;
+    }
+
+    pub(super) fn fill(chunk: &mut [u8]) -> bool {
+        // SAFETY: `chunk` is valid for writes of its length, at most 256 bytes
+        unsafe { getentropy(chunk.as_mut_ptr(), chunk.len()) == 0 }
+    }
+}
+
+#[cfg(all(any(feature = "std", feature = "getrandom"), windows))]
+mod os {
+    use core::ffi::c_void;
+
+    const BCRYPT_USE_SYSTEM_PREFERRED_RNG: u32 = 0x00000002;
+
+    #[link(name = "bcrypt")]
+    unsafe extern "system" {
+        fn BCryptGenRandom(
+            hAlgorithm: *mut c_void,
+            pbBuffer: *mut u8,
+            cbBuffer: u32,
+            dwFlags: u32,
+        ) -> i32;
+    }
+
+    pub(super) fn fill(chunk: &mut [u8]) -> bool {
+        // SAFETY: `chunk` is valid for writes of its length, which fits in a u32
+        let status = unsafe {
+            BCryptGenRandom(
+                core::ptr::null_mut(),
+                chunk.as_mut_ptr(),
+                chunk.len() as u32,
+                BCRYPT_USE_SYSTEM_PREFERRED_RNG,
+            )
+        };
+        status == 0
+    }
+}
+
+#[cfg(all(any(feature = "std", feature = "getrandom"), target_os = "wasi"))]
+mod os {
+    #[link(wasm_import_module = "wasi_snapshot_preview1")]
+    unsafe extern "C" {
+        fn random_get(buf: *mut u8, buf_len: usize) -> i32;
+    }
+
+    pub(super) fn fill(chunk: &mut [u8]) -> bool {
+        // SAFETY: `chunk` is valid for writes of its length
+        unsafe { random_get(chunk.as_mut_ptr(), chunk.len()) == 0 }
+    }
+}
+
+// The browser or other host provides this import, see the `wasm` feature in
+// the README for the JavaScript side
+#[cfg(all(
+    any(feature = "std", feature = "getrandom"),
+    target_arch = "wasm32",
+    target_os = "unknown",
+    feature = "wasm"
+))]
+mod os {
+    #[link(wasm_import_module = "simple_rng")]
+    unsafe extern "C" {
+        fn fill_random(buf: *mut u8, len: usize) -> i32;
+    }
+
+    pub(super) fn fill(chunk: &mut [u8]) -> bool {
+        // SAFETY: `chunk` is valid for writes of its length
+        unsafe { fill_random(chunk.as_mut_ptr(), chunk.len()) == 0 }
+    }
+}
+
+#[cfg(all(
+    any(feature = "std", feature = "getrandom"),
+    not(any(
+        target_os = "linux",
+        target_os = "android",
+        target_vendor = "apple",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "dragonfly",
+        target_os = "wasi",
+        all(target_arch = "wasm32", target_os = "unknown", feature = "wasm"),
+        windows
+    ))
+))]
+mod os {
+    pub(super) fn fill(_chunk: &mut [u8]) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "getrandom")]
+impl RNG {
+    /// Create a new RNG seeded from the operating system's CSPRNG, without `std`
+    ///
+    /// Calls `getrandom` on Linux and Android, `getentropy` on Apple targets and
+    /// the BSDs, `BCryptGenRandom` on Windows and `random_get` on WASI. On
+    /// `wasm32-unknown-unknown` the `wasm` feature reads the host's Web Crypto
+    /// through a small JavaScript import. Returns
+    /// [`RngError::EntropyUnavailable`] on other targets or if the call fails.
+    /// Only available with the `getrandom` feature.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::from_os_entropy().expect("no OS entropy");
+    /// let value = rng.next();
+    /// println!("{}", value);
+    /// ```
+    pub fn from_os_entropy() -> Result<Self, RngError> {
+        let mut buf = [0u8; 8];
+        if !fill_os_entropy(&mut buf) {
+            return Err(RngError::EntropyUnavailable);
+        }
+        Ok(Self::new(u64::from_le_bytes(buf)))
+    }
+}
+
+impl RNG {
+    /// Create a new RNG seeded with eight bytes from `source`
+    ///
+    /// For a typed generator, fill a 32-byte seed from the source and pass it
+    /// to [`RNG::from_seed_bytes`], as in the [`EntropySource`] example.
+    pub fn from_source<S: EntropySource + ?Sized>(source: &mut S) -> Self {
+        let mut buf = [0u8; 8];
+        source.fill(&mut buf);
+        Self::new(u64::from_le_bytes(buf))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Closures work as sources and the seed is the first eight bytes
+    fn test_from_source() {
+        let mut calls = 0;
+        let mut rng = RNG::from_source(&mut |dest: &mut [u8]| {
+            calls += 1;
+            dest.copy_from_slice(&42u64.to_le_bytes());
+        });
+        assert_eq!(rng.next(), RNG::new(42).next());
+        assert_eq!(calls, 1);
+    }
+
+    // A counter that ticks by a varying amount, like a CPU cycle counter
+    fn jittery_counter() -> impl FnMut() -> u64 {
+        let mut cycles = 0u64;
+        let mut noise = 0x9E37_79B9u64;
+        move || {
+            noise ^= noise << 13;
+            noise ^= noise >> 7;
+            noise ^= noise << 17;
+            cycles += 90 + noise % 16;
+            cycles
+        }
+    }
+
+    #[test]
+    /// Jitter fills differ from call to call, and stuck counters are rejected
+    fn test_jitter_entropy() {
+        let mut jitter = JitterEntropy::new(jittery_counter()).unwrap();
+        let mut first = [0u8; 20];
+        let mut second = [0u8; 20];
+        jitter.fill(&mut first);
+        jitter.fill(&mut second);
+        assert_ne!(first, second);
+        assert_ne!(first, [0; 20]);
+
+        assert!(JitterEntropy::new(|| 7).is_err());
+        let mut steady = 0;
+        let counter = move || {
+            steady += 100;
+            steady
+        };
+        assert_eq!(
+            JitterEntropy::new(counter).err(),
+            Some(RngError::EntropyUnavailable)
+        );
+    }
+
+    #[test]
+    #[cfg(all(
+        any(feature = "std", feature = "getrandom"),
+        any(target_os = "linux", target_os = "android", windows)
+    ))]
+    /// Reads succeed, and two reads of OS entropy differ
+    fn test_os_entropy() {
+        let mut first = [0u8; 300];
+        let mut second = [0u8; 300];
+        assert!(fill_os_entropy(&mut first));
+        assert!(fill_os_entropy(&mut second));
+        assert_ne!(first, second);
+        assert_ne!(
+            RNG::from_source(&mut OsEntropy).next(),
+            RNG::from_source(&mut OsEntropy).next()
+        );
+        #[cfg(feature = "getrandom")]
+        assert!(RNG::from_os_entropy().is_ok());
+    }
+}
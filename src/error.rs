@@ -0,0 +1,40 @@
+//! Errors from the non-panicking `try_*` methods and state decoding, see [`RngError`].
+
+use core::fmt;
+
+/// Why a `try_*` method such as [`crate::RNG::try_gen_range`] could not generate a value,
+/// [`crate::RNG::from_bytes`] could not restore a state, or no entropy could be read
+///
+/// The panicking methods panic with the same message as this error's
+/// `Display` output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RngError {
+    /// The range holds no values, such as `5..5` or `10..=1`
+    EmptyRange,
+    /// A float range bound is infinite or NaN
+    NonFiniteBound,
+    /// A probability is outside [0.0, 1.0] or NaN
+    InvalidProbability,
+    /// A ratio has a zero denominator or a numerator larger than it
+    InvalidRatio,
+    /// Bytes passed to [`crate::RNG::from_bytes`] are not a state encoding this build can read
+    InvalidState,
+    /// The operating system's entropy source, or a jitter timer, is missing or failed
+    EntropyUnavailable,
+}
+
+impl fmt::Display for RngError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RngError::EmptyRange => "cannot sample empty range",
+            RngError::NonFiniteBound => "range bounds must be finite",
+            RngError::InvalidProbability => "probability must be between 0 and 1",
+            RngError::InvalidRatio => "ratio must be in [0, 1] with a non-zero denominator",
+            RngError::InvalidState => "invalid or unsupported state encoding",
+            RngError::EntropyUnavailable => "no entropy source is available",
+        })
+    }
+}
+
+impl core::error::Error for RngError {}
@@ -0,0 +1,76 @@
+//! C interface to the default generator (requires `ffi` feature).
+//!
+//! `make ffi` builds a shared and a static library into `target/release`, and
+//! `include/simple_rng.h` declares the functions for C and C++. The crate type
+//! is chosen at build time rather than in `Cargo.toml`, so `no_std` users of
+//! the rlib don't need a panic handler for a cdylib they never use.
+//! `make ffi-check` links a small C program against the static library.
+//!
+//! A handle seeded with `seed` produces exactly the sequence of
+//! [`RNG::new`]`(seed)`, so C code and Rust code can share runs.
+
+use crate::RNG;
+use alloc::boxed::Box;
+
+/// Create a generator seeded with `seed`, to be released with [`simplerng_free`]
+#[unsafe(no_mangle)]
+pub extern "C" fn simplerng_new(seed: u64) -> *mut RNG {
+    Box::into_raw(Box::new(RNG::new(seed)))
+}
+
+/// Next random u64, see [`RNG::next`]
+///
+/// # Safety
+/// `rng` must come from [`simplerng_new`], not yet freed, and not be in use
+/// on another thread.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn simplerng_next(rng: *mut RNG) -> u64 {
+    // SAFETY: the caller passes a live handle with no other users
+    unsafe { &mut *rng }.next()
+}
+
+/// Random integer in [min, max] (inclusive), see [`RNG::gen_range`]
+///
+/// Returns `min` without drawing if `max < min`, since a panic cannot cross
+/// into C.
+///
+/// # Safety
+/// Same as [`simplerng_next`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn simplerng_range(rng: *mut RNG, min: u64, max: u64) -> u64 {
+    // SAFETY: the caller passes a live handle with no other users
+    let rng = unsafe { &mut *rng };
+    rng.try_gen_range(min..=max).unwrap_or(min)
+}
+
+/// Release a generator created by [`simplerng_new`], doing nothing for null
+///
+/// # Safety
+/// `rng` must be null or come from [`simplerng_new`] and not be used again.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn simplerng_free(rng: *mut RNG) {
+    if !rng.is_null() {
+        // SAFETY: the handle was created by `Box::into_raw` and is freed once
+        drop(unsafe { Box::from_raw(rng) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// A handle follows the same sequence as the Rust generator
+    fn test_ffi_matches_rng() {
+        let mut plain = RNG::new(42);
+        let handle = simplerng_new(42);
+        unsafe {
+            assert_eq!(simplerng_next(handle), plain.next());
+            assert_eq!(simplerng_range(handle, 1, 6), plain.gen_range(1..=6));
+            assert_eq!(simplerng_range(handle, 9, 3), 9);
+            assert_eq!(simplerng_next(handle), plain.next());
+            simplerng_free(handle);
+            simplerng_free(core::ptr::null_mut());
+        }
+    }
+}
@@ -0,0 +1,303 @@
+//! Fortuna-style entropy accumulator, see [`Accumulator`].
+
+use crate::RNG;
+use crate::algorithms::sha256::{DIGEST_LEN, Sha256};
+#[cfg(feature = "zeroize")]
+use crate::algorithms::wipe;
+
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+/// Number of entropy pools, as in Ferguson and Schneier's design
+const POOL_COUNT: usize = 32;
+/// Bytes pool 0 must collect before a reseed is allowed
+const MIN_POOL_SIZE: usize = 64;
+/// Largest single entropy event in bytes
+const MAX_EVENT_LEN: usize = 32;
+/// Shortest interval between reseeds
+#[cfg(feature = "std")]
+const RESEED_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Clone, Debug, PartialEq)]
+struct Pool {
+    hasher: Sha256,
+    length: usize,
+}
+
+/// Fortuna entropy accumulator that periodically reseeds an [`RNG`]
+///
+/// Entropy events are spread over 32 pools. Each source cycles through the pools
+/// on its own, so a source that floods the accumulator cannot starve the others.
+/// Reseed number `n` drains pool `i` only when `2^i` divides `n`. That means the
+/// higher pools build up entropy for longer, and the generator eventually
+/// recovers even if some sources are weak or controlled by an attacker.
+///
+/// A reseed needs at least 64 bytes in pool 0. With the `std` feature, reseeds are
+/// also at least 100 ms apart. DRBG algorithms get the derived key as entropy
+/// input, and ChaCha hashes it together with its current 256-bit key into a new
+/// one. Every other algorithm is reseeded from the key folded into its current
+/// output. Only available with the `fortuna` feature.
+///
+/// # Example
+/// ```rust
+/// use simple_rng::{Accumulator, RNG};
+/// let mut rng = RNG::new(12345);
+/// let mut accumulator = Accumulator::new();
+/// for i in 0..64u8 {
+///     accumulator.add_event(0, &[i; 32]);
+/// }
+/// let reseeded = accumulator.reseed(&mut rng);
+/// println!("reseeded: {}", reseeded);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Accumulator {
+    pools: [Pool; POOL_COUNT],
+    next_pool: [u8; 256],
+    key: [u8; DIGEST_LEN],
+    reseed_count: u64,
+    #[cfg(feature = "std")]
+    last_reseed: Option<Instant>,
+}
+
+impl Accumulator {
+    /// Create an accumulator with empty pools
+    pub fn new() -> Self {
+        Self {
+            pools: core::array::from_fn(|_| Pool {
+                hasher: Sha256::new(),
+                length: 0,
+            }),
+            next_pool: [0; 256],
+            key: [0; DIGEST_LEN],
+            reseed_count: 0,
+            #[cfg(feature = "std")]
+            last_reseed: None,
+        }
+    }
+
+    /// Add an entropy event of 1 to 32 bytes from `source`
+    ///
+    /// Panics if `data` is empty or longer than 32 bytes.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::Accumulator;
+    /// let mut accumulator = Accumulator::new();
+    /// accumulator.add_event(3, b"interrupt timing");
+    /// ```
+    pub fn add_event(&mut self, source: u8, data: &[u8]) {
+        if data.is_empty() || data.len() > MAX_EVENT_LEN {
+            panic!("entropy event must be 1 to 32 bytes")
+        }
+        let cursor = &mut self.next_pool[source as usize];
+        let pool = &mut self.pools[*cursor as usize];
+        *cursor = (*cursor + 1) % POOL_COUNT as u8;
+
+        pool.hasher.update(&[source, data.len() as u8]);
+        pool.hasher.update(data);
+        pool.length = pool.length.saturating_add(2 + data.len());
+    }
+
+    /// Whether enough entropy has been collected for [`Accumulator::reseed`]
+    pub fn is_ready(&self) -> bool {
+        #[cfg(feature = "std")]
+        if let Some(last) = self.last_reseed
+            && last.elapsed() < RESEED_INTERVAL
+        {
+            return false;
+        }
+        self.pools[0].length >= MIN_POOL_SIZE
+    }
+
+    /// Number of reseeds performed so far
+    pub fn reseed_count(&self) -> u64 {
+        self.reseed_count
+    }
+
+    /// Reseed the active algorithm of `rng` if the accumulator is ready
+    ///
+    /// Returns `false`, leaving `rng` untouched, when pool 0 is still too small or
+    /// the last reseed was too recent.
+    pub fn reseed(&mut self, rng: &mut RNG) -> bool {
+        if !self.is_ready() {
+            return false;
+        }
+        self.reseed_count += 1;
+
+        let mut hasher = Sha256::new();
+        hasher.update(&self.key);
+        for (i, pool) in self.pools.iter_mut().enumerate() {
+            if !self.reseed_count.is_multiple_of(1 << i) {
+                break;
+            }
+            let drained = core::mem::replace(&mut pool.hasher, Sha256::new());
+            hasher.update(&drained.finalize());
+            pool.length = 0;
+        }
+        let mut outer = Sha256::new();
+        outer.update(&hasher.finalize());
+        self.key = outer.finalize();
+
+        #[cfg(feature = "std")]
+        {
+            self.last_reseed = Some(Instant::now());
+        }
+        rng.state.0.reseed(&self.key);
+        rng.cached_u32 = None;
+        true
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Accumulator {
+    /// Overwrite the key and every pool with zeros, leaving an empty
+    /// accumulator (requires `zeroize` feature)
+    ///
+    /// Dropping the accumulator does this automatically. The writes cannot be
+    /// optimised away, but copies left behind by `clone` or by moving the
+    /// value are not touched.
+    pub fn zeroize(&mut self) {
+        wipe(&mut self.key, [0; DIGEST_LEN]);
+        for pool in &mut self.pools {
+            pool.hasher.zeroize();
+            pool.hasher = Sha256::new();
+            wipe(&mut pool.length, 0);
+        }
+        wipe(&mut self.next_pool, [0; 256]);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Accumulator {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl Default for Accumulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Algorithm;
+    #[cfg(feature = "crypto")]
+    use crate::algorithms::{State, fold_seed};
+
+    // Fill pool 0 past MIN_POOL_SIZE from a single source
+    fn fill(accumulator: &mut Accumulator) {
+        for i in 0..=POOL_COUNT as u8 {
+            accumulator.add_event(7, &[i; MAX_EVENT_LEN]);
+        }
+    }
+
+    #[test]
+    /// No reseed happens until pool 0 has collected enough entropy
+    fn test_reseed_requires_pool_zero() {
+        let mut accumulator = Accumulator::new();
+        let mut rng = RNG::new(42);
+        let before = rng.state.clone();
+        accumulator.add_event(0, &[1; 32]);
+        assert!(!accumulator.reseed(&mut rng));
+        assert_eq!(rng.state, before);
+        assert_eq!(accumulator.reseed_count(), 0);
+    }
+
+    #[test]
+    /// Reseeding changes the stream but keeps the algorithm
+    fn test_reseed_changes_stream() {
+        let mut accumulator = Accumulator::new();
+        let mut rng = RNG::with_algorithm(42, Algorithm::Xoshiro256PlusPlus);
+        let mut untouched = RNG::with_algorithm(42, Algorithm::Xoshiro256PlusPlus);
+        fill(&mut accumulator);
+        assert!(accumulator.reseed(&mut rng));
+        assert_eq!(rng.state.0.algorithm(), Algorithm::Xoshiro256PlusPlus);
+        assert_ne!(rng.next(), untouched.next());
+        assert_eq!(accumulator.reseed_count(), 1);
+    }
+
+    #[test]
+    /// The same events and starting state lead to the same reseeded stream
+    fn test_reseed_is_deterministic() {
+        let mut first = Accumulator::new();
+        let mut second = Accumulator::new();
+        fill(&mut first);
+        fill(&mut second);
+        let mut a = RNG::new(1);
+        let mut b = RNG::new(1);
+        first.reseed(&mut a);
+        second.reseed(&mut b);
+        assert_eq!(a.state, b.state);
+    }
+
+    #[test]
+    #[should_panic(expected = "entropy event must be 1 to 32 bytes")]
+    /// Events longer than 32 bytes are rejected
+    fn test_rejects_long_event() {
+        Accumulator::new().add_event(0, &[0; 33]);
+    }
+
+    #[cfg(feature = "drbg")]
+    #[test]
+    /// DRBGs are reseeded through their own reseed function
+    fn test_reseed_drbg() {
+        let mut accumulator = Accumulator::new();
+        let mut rng = RNG::instantiate_drbg(Algorithm::HmacDrbg, &[7; 32], b"nonce", b"");
+        let mut expected = RNG::instantiate_drbg(Algorithm::HmacDrbg, &[7; 32], b"nonce", b"");
+        fill(&mut accumulator);
+        assert!(accumulator.reseed(&mut rng));
+        expected.reseed_drbg(&accumulator.key, &[]);
+        assert_eq!(rng.state, expected.state);
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    /// ChaCha mixes the pool key into its full key instead of a 64-bit seed
+    fn test_reseed_chacha() {
+        let mut accumulator = Accumulator::new();
+        let mut rng = RNG::with_algorithm(3, Algorithm::ChaCha20);
+        let mut expected = RNG::with_algorithm(3, Algorithm::ChaCha20);
+        fill(&mut accumulator);
+        assert!(accumulator.reseed(&mut rng));
+        expected.state.0.rekey(&accumulator.key);
+        assert_eq!(rng.state, expected.state);
+        let folded = State::new(Algorithm::ChaCha20, fold_seed(&accumulator.key));
+        assert_ne!(rng.state.0, folded);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    /// zeroize clears the key and empties every pool
+    fn test_zeroize() {
+        let mut accumulator = Accumulator::new();
+        fill(&mut accumulator);
+        assert!(accumulator.reseed(&mut RNG::new(5)));
+        fill(&mut accumulator);
+        accumulator.zeroize();
+        assert_eq!(accumulator.key, [0; DIGEST_LEN]);
+        assert!(accumulator.pools.iter().all(|pool| pool.length == 0));
+        assert!(
+            accumulator
+                .pools
+                .iter()
+                .all(|pool| pool.hasher == Sha256::new())
+        );
+        assert_eq!(accumulator.next_pool, [0; 256]);
+        assert!(!accumulator.is_ready());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    /// A second reseed right after the first is refused
+    fn test_reseed_interval() {
+        let mut accumulator = Accumulator::new();
+        let mut rng = RNG::new(42);
+        fill(&mut accumulator);
+        assert!(accumulator.reseed(&mut rng));
+        fill(&mut accumulator);
+        assert!(!accumulator.reseed(&mut rng));
+    }
+}
@@ -0,0 +1,92 @@
+//! Infinite iterators over generator outputs, see [`RNG::iter`],
+//! [`RNG::iter_range`] and [`RNG::sample_iter`].
+
+use crate::{RNG, Random, RngAlgorithm, SampleUniform, Uniform};
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+/// Endless iterator of u64 outputs, created by [`RNG::iter`]
+pub struct Iter<'a, A: RngAlgorithm> {
+    pub(crate) rng: &'a mut RNG<A>,
+}
+
+impl<A: RngAlgorithm> Iterator for Iter<'_, A> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        Some(self.rng.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<A: RngAlgorithm> FusedIterator for Iter<'_, A> {}
+
+/// Endless iterator of values in a range, created by [`RNG::iter_range`]
+pub struct IterRange<'a, A: RngAlgorithm, T> {
+    pub(crate) rng: &'a mut RNG<A>,
+    pub(crate) uniform: Uniform<T>,
+}
+
+impl<A: RngAlgorithm, T: SampleUniform> Iterator for IterRange<'_, A, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        Some(self.uniform.sample(self.rng))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<A: RngAlgorithm, T: SampleUniform> FusedIterator for IterRange<'_, A, T> {}
+
+/// Endless iterator of [`Random`] values, created by [`RNG::sample_iter`]
+pub struct SampleIter<'a, A: RngAlgorithm, T> {
+    pub(crate) rng: &'a mut RNG<A>,
+    pub(crate) marker: PhantomData<fn() -> T>,
+}
+
+impl<A: RngAlgorithm, T: Random> Iterator for SampleIter<'_, A, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        Some(self.rng.random())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<A: RngAlgorithm, T: Random> FusedIterator for SampleIter<'_, A, T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::RNG;
+
+    #[test]
+    /// The iterators yield the same values as the methods they wrap
+    fn test_iterators_match_methods() {
+        let mut plain = RNG::new(3);
+        let mut rng = RNG::new(3);
+        for value in rng.iter().take(5) {
+            assert_eq!(value, plain.next());
+        }
+        let rolls: [u8; 8] = core::array::from_fn(|_| plain.gen_range(1..=6));
+        assert!(rng.iter_range(1..=6).take(8).eq(rolls));
+        let pairs: [(bool, i16); 3] = core::array::from_fn(|_| plain.random());
+        assert!(rng.sample_iter::<(bool, i16)>().take(3).eq(pairs));
+        assert_eq!(rng.next(), plain.next());
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot sample empty range")]
+    /// An empty range panics when the iterator is created
+    fn test_iter_range_empty() {
+        RNG::new(0).iter_range(5..5);
+    }
+}
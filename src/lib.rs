@@ -1,13 +1,118 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 #[cfg(feature = "std")]
 extern crate std;
 
 #[cfg(feature = "std")]
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::SystemTime;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+// Resolve a `RangeBounds` of an integer type to inclusive `(low, high)`
+// bounds, or `None` if the range holds no values
+macro_rules! inclusive_bounds {
+    ($range:expr, $ty:ty) => {{
+        use core::ops::Bound;
+        let range = $range;
+        let low = match range.start_bound() {
+            Bound::Included(&low) => Some(low),
+            Bound::Excluded(&low) => low.checked_add(1),
+            Bound::Unbounded => Some(<$ty>::MIN),
+        };
+        let high = match range.end_bound() {
+            Bound::Included(&high) => Some(high),
+            Bound::Excluded(&high) => high.checked_sub(1),
+            Bound::Unbounded => Some(<$ty>::MAX),
+        };
+        match (low, high) {
+            (Some(low), Some(high)) if low <= high => Some((low, high)),
+            _ => None,
+        }
+    }};
+}
+
+mod algorithms;
+#[cfg(target_has_atomic = "64")]
+mod atomic;
+mod buffered;
+mod builder;
+#[cfg(feature = "alloc")]
+mod digits;
+mod entropy;
+mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "fortuna")]
+mod fortuna;
+mod iter;
+#[cfg(feature = "alloc")]
+mod prime;
+mod random;
+#[cfg(feature = "std")]
+mod random_bytes;
+mod reseeding;
+mod rng32;
+mod rng8;
+mod seed_seq;
+mod snapshot;
+#[cfg(feature = "std")]
+mod sync_rng;
+#[cfg(feature = "std")]
+mod thread_rng;
+mod uniform;
 
+#[cfg(feature = "isaac")]
+pub use algorithms::Isaac64;
+#[cfg(feature = "mt19937")]
+pub use algorithms::Mt19937_64;
+use algorithms::State;
+#[cfg(feature = "simd")]
+pub use algorithms::Xoshiro256PlusPlusX4;
+use algorithms::portable::Portable;
+#[cfg(feature = "crypto")]
+pub use algorithms::{ChaCha, ChaCha8, ChaCha12, ChaCha20};
+#[cfg(feature = "drbg")]
+pub use algorithms::{CtrDrbg, HashDrbg, HmacDrbg};
+pub use algorithms::{
+    Jsf64, Lcg, Mcg128, Msws, Mwc256, Philox4x32, Sfc64, SplitMix64, Threefry2x64, WyRand,
+    Xoroshiro128Plus, Xorshift64Star, Xoshiro128PlusPlus, Xoshiro256PlusPlus,
+};
+#[cfg(feature = "pcg")]
+pub use algorithms::{Pcg, Pcg64};
+#[cfg(feature = "romu")]
+pub use algorithms::{RomuDuoJr, RomuTrio};
+#[cfg(target_has_atomic = "64")]
+pub use atomic::AtomicRng;
+pub use buffered::Buffered;
+pub use builder::RngBuilder;
+use core::marker::PhantomData;
+use core::num::NonZeroU64;
+use core::ops::RangeBounds;
+use core::time::Duration;
+#[cfg(any(feature = "std", feature = "getrandom"))]
+pub use entropy::OsEntropy;
+pub use entropy::{EntropySource, JitterEntropy};
+pub use error::RngError;
+#[cfg(feature = "fortuna")]
+pub use fortuna::Accumulator;
+pub use iter::{Iter, IterRange, SampleIter};
+pub use random::Random;
 #[cfg(feature = "std")]
-use std::process;
+pub use random_bytes::RandomBytes;
+pub use reseeding::Reseeding;
+pub use rng8::RNG8;
+pub use rng32::RNG32;
+pub use seed_seq::SeedSeq;
+pub use snapshot::Snapshot;
+#[cfg(feature = "std")]
+pub use sync_rng::SyncRng;
+#[cfg(feature = "std")]
+pub use thread_rng::{ThreadRng, random, random_range, shuffle, thread_rng};
+pub use uniform::{SampleFloat, SampleUniform, Uniform};
 
 /// Supported random number generator algorithms
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -17,10 +122,166 @@ pub enum Algorithm {
     /// Permuted Congruential Generator (requires `pcg` feature)
     #[cfg(feature = "pcg")]
     Pcg,
+    /// xoshiro256++ with 256 bits of state
+    Xoshiro256PlusPlus,
+    /// xoroshiro128+ with 128 bits of state
+    Xoroshiro128Plus,
+    /// SplitMix64, also used to expand seeds for the larger-state algorithms
+    SplitMix64,
+    /// Xorshift64* with a single 64-bit word of state
+    Xorshift64Star,
+    /// PCG-XSL-RR 128/64 with 128 bits of state (requires `pcg` feature)
+    #[cfg(feature = "pcg")]
+    Pcg64,
+    /// 64-bit Mersenne Twister, compatible with `std::mt19937_64` (requires `mt19937` feature)
+    #[cfg(feature = "mt19937")]
+    Mt19937_64,
+    /// WyRand, a very fast generator with a single 64-bit word of state
+    WyRand,
+    /// SFC64 (Small Fast Chaotic) with 256 bits of state
+    Sfc64,
+    /// JSF64, Bob Jenkins' small fast generator with 256 bits of state
+    Jsf64,
+    /// RomuDuoJr with 128 bits of state (requires `romu` feature)
+    #[cfg(feature = "romu")]
+    RomuDuoJr,
+    /// RomuTrio with 192 bits of state (requires `romu` feature)
+    #[cfg(feature = "romu")]
+    RomuTrio,
+    /// Lehmer multiplicative congruential generator with 128 bits of state
+    Mcg128,
+    /// Philox4x32-10 counter-based generator, supports [`RNG::seek`]
+    Philox4x32,
+    /// Threefry2x64-20 counter-based generator, supports [`RNG::seek`]
+    Threefry2x64,
+    /// MWC256 multiply-with-carry generator with 256 bits of state
+    Mwc256,
+    /// Middle Square Weyl Sequence with 192 bits of state
+    Msws,
+    /// ChaCha20, cryptographically secure (requires `crypto` feature)
+    #[cfg(feature = "crypto")]
+    ChaCha20,
+    /// ChaCha with 12 rounds (requires `crypto` feature)
+    #[cfg(feature = "crypto")]
+    ChaCha12,
+    /// ChaCha with 8 rounds (requires `crypto` feature)
+    #[cfg(feature = "crypto")]
+    ChaCha8,
+    /// AES-256 CTR_DRBG from NIST SP 800-90A (requires `drbg` feature)
+    #[cfg(feature = "drbg")]
+    CtrDrbg,
+    /// HMAC_DRBG with SHA-256 from NIST SP 800-90A (requires `drbg` feature)
+    #[cfg(feature = "drbg")]
+    HmacDrbg,
+    /// Hash_DRBG with SHA-256 from NIST SP 800-90A (requires `drbg` feature)
+    #[cfg(feature = "drbg")]
+    HashDrbg,
+    /// ISAAC64 by Bob Jenkins, compatible with the reference `isaac64.c` (requires `isaac` feature)
+    #[cfg(feature = "isaac")]
+    Isaac64,
+    /// xoshiro128++ with 128 bits of state in 32-bit words, the generator behind [`RNG32`]
+    Xoshiro128PlusPlus,
+}
+
+/// A source of random 64-bit words that [`RNG`] can wrap
+///
+/// Implement this to use the `RNG` helpers, such as [`RNG::gen_range`], with your
+/// own generator, for example one backed by hardware.
+///
+/// # Example
+/// ```rust
+/// use simple_rng::{RNG, RngAlgorithm};
+///
+/// struct Counter(u64);
+///
+/// impl RngAlgorithm for Counter {
+///     fn next_u64(&mut self) -> u64 {
+///         self.0 += 1;
+///         self.0
+///     }
+/// }
+///
+/// let mut rng = RNG::from_generator(Counter(0));
+/// assert_eq!(rng.next(), 1);
+/// ```
+pub trait RngAlgorithm {
+    /// Advance the generator and return the next random u64 value
+    fn next_u64(&mut self) -> u64;
+
+    /// Return the next random u128 value
+    ///
+    /// The default joins two consecutive outputs, the first in the low half.
+    /// Generators with a native 128-bit output can override it.
+    fn next_u128(&mut self) -> u128 {
+        let low = self.next_u64() as u128;
+        let high = self.next_u64() as u128;
+        (high << 64) | low
+    }
+
+    /// Fill `dest` with consecutive outputs
+    ///
+    /// The default calls [`RngAlgorithm::next_u64`] once per word. Block-based
+    /// generators such as ChaCha override it to write whole blocks into `dest`.
+    fn fill_u64(&mut self, dest: &mut [u64]) {
+        for word in dest {
+            *word = self.next_u64();
+        }
+    }
+}
+
+/// An [`RngAlgorithm`] that can be created from a 64-bit seed or 32 seed bytes
+///
+/// Every built-in generator implements this, so the algorithm can be fixed at
+/// compile time with [`RNG::from_seed`].
+pub trait SeedableAlgorithm: RngAlgorithm {
+    /// Create the generator from `seed`, expanding it to the full state
+    fn from_seed(seed: u64) -> Self;
+
+    /// Create the generator from 32 seed bytes, such as a hash or key material
+    ///
+    /// Xoshiro256++ and ChaCha use the bytes as their state or key directly,
+    /// and the DRBGs as their entropy input. Other generators fold them into a 64-bit seed for
+    /// [`SeedableAlgorithm::from_seed`] unless they override this.
+    fn from_seed_bytes(seed: [u8; 32]) -> Self
+    where
+        Self: Sized,
+    {
+        Self::from_seed(algorithms::fold_seed(&seed))
+    }
+}
+
+/// Built-in generator selected at runtime through [`Algorithm`]
+///
+/// This is the default generator of [`RNG`], created by [`RNG::new`] and
+/// [`RNG::with_algorithm`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnyAlgorithm(State);
+
+impl RngAlgorithm for AnyAlgorithm {
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_u64(&mut self, dest: &mut [u64]) {
+        self.0.fill_u64(dest)
+    }
+}
+
+/// An [`RngAlgorithm`] that calls a closure for each output, created by [`RNG::from_fn`]
+#[derive(Clone, Debug)]
+pub struct FromFn<F>(F);
+
+impl<F: FnMut() -> u64> RngAlgorithm for FromFn<F> {
+    fn next_u64(&mut self) -> u64 {
+        (self.0)()
+    }
 }
 
 /// A simple, seedable pseudo-random number generator
 ///
+/// By default the algorithm is picked at runtime from [`Algorithm`]. Any other
+/// [`RngAlgorithm`] can be wrapped with [`RNG::from_generator`].
+///
 /// # Example
 /// ```rust
 /// use simple_rng::RNG;
@@ -28,29 +289,134 @@ pub enum Algorithm {
 /// let value = rng.next();
 /// println!("{}", value);
 /// ```
-pub struct RNG {
-    seed: u64,
-    algorithm: Algorithm,
+pub struct RNG<A: RngAlgorithm = AnyAlgorithm> {
+    state: A,
+    // High half of the last output split by `next_u32`
+    cached_u32: Option<u32>,
+}
+
+/// The default LCG seeded with 0, the same as `RNG::builder().build()`
+///
+/// Use [`RNG::from_time`] or [`RNG::from_entropy`] for a seed that varies
+/// between runs.
+impl Default for RNG {
+    fn default() -> Self {
+        Self::new(0)
+    }
 }
 
 impl RNG {
+    /// Start configuring an RNG step by step, see [`RngBuilder`]
+    pub fn builder() -> RngBuilder {
+        RngBuilder::new()
+    }
+
     /// Create a new RNG with the given seed
     ///
+    /// This is a `const fn`, so a seeded generator can live in a `static`
+    /// without lazy initialization.
+    ///
     /// # Example
     /// ```rust
     /// use simple_rng::RNG;
-    /// let mut rng = RNG::new(84);
+    /// use std::sync::Mutex;
+    ///
+    /// static GLOBAL: Mutex<RNG> = Mutex::new(RNG::new(84));
+    ///
+    /// let value = GLOBAL.lock().unwrap().next();
+    /// println!("{}", value);
     /// ```
-    pub fn new(seed: u64) -> Self {
-        Self {
-            seed,
-            algorithm: Algorithm::Lcg,
+    pub const fn new(seed: u64) -> Self {
+        Self::from_generator(AnyAlgorithm(State::Lcg(Lcg::new(seed))))
+    }
+
+    /// Create a new RNG seeded from a string, for generation keyed by a name
+    ///
+    /// The string is hashed with 64-bit FNV-1a, so the same string gives the
+    /// same sequence on every platform and in every version. Like
+    /// [`RNG::new`], this is a `const fn`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut level = RNG::from_str_seed("player-name-level-3");
+    /// let layout = level.next();
+    /// println!("{}", layout);
+    /// ```
+    pub const fn from_str_seed(seed: &str) -> Self {
+        let bytes = seed.as_bytes();
+        let mut hash: u64 = 0xcbf29ce484222325;
+        let mut i = 0;
+        while i < bytes.len() {
+            hash ^= bytes[i] as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+            i += 1;
+        }
+        Self::new(hash)
+    }
+
+    /// Create a new RNG with the given seed and algorithm
+    ///
+    /// Unlike [`RNG::new`] this is not a `const fn`, since several algorithms
+    /// run key schedules or table setup that can't be evaluated at compile time.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::{Algorithm, RNG};
+    /// let mut rng = RNG::with_algorithm(84, Algorithm::Xoroshiro128Plus);
+    /// ```
+    pub fn with_algorithm(seed: u64, algorithm: Algorithm) -> Self {
+        Self::from_generator(AnyAlgorithm(State::new(algorithm, seed)))
+    }
+
+    /// Create a new RNG with the given seed on an independent stream
+    ///
+    /// Generators with the same seed but different streams produce different
+    /// sequences. The default LCG uses the stream to pick its increment. Like
+    /// [`RNG::new`], this is a `const fn`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut worker_a = RNG::new_with_stream(84, 1);
+    /// let mut worker_b = RNG::new_with_stream(84, 2);
+    /// assert_ne!(worker_a.next(), worker_b.next());
+    /// ```
+    pub const fn new_with_stream(seed: u64, stream: u64) -> Self {
+        Self::from_generator(AnyAlgorithm(State::Lcg(Lcg::with_stream(seed, stream))))
+    }
+
+    /// Create a new RNG with the given seed and algorithm on an independent stream
+    ///
+    /// The stream selects the increment of `Lcg`, `Pcg` and `Pcg64`, the key of
+    /// `Threefry2x64`, the high counter half of `Philox4x32` and the stream word of
+    /// ChaCha. Stream 0 gives the same sequence as [`RNG::with_algorithm`], except
+    /// for the PCG algorithms, which follow the reference `srandom` seeding. Panics
+    /// if the algorithm has no streams.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::{Algorithm, RNG};
+    /// let mut rng = RNG::with_algorithm_and_stream(84, 3, Algorithm::Philox4x32);
+    /// let value = rng.next();
+    /// println!("{}", value);
+    /// ```
+    pub fn with_algorithm_and_stream(seed: u64, stream: u64, algorithm: Algorithm) -> Self {
+        match State::with_stream(algorithm, seed, stream) {
+            Some(state) => Self::from_generator(AnyAlgorithm(state)),
+            None => panic!("Algorithm does not support streams"),
         }
     }
 
     /// Create a new RNG seeded from the current system time
     ///
-    /// Only available with the `std` feature.
+    /// Never panics: if the clock is set before 1970 the distance back to the
+    /// epoch is used instead, mixed with a call counter and a stack address so
+    /// back-to-back calls still get different seeds. On `wasm32-unknown-unknown`,
+    /// where `std` has no clock, the seed comes from the host's Web Crypto with
+    /// the `wasm` feature. Without it only the counter and stack address are
+    /// left, so every page load gets the same seeds. Only available with the
+    /// `std` feature.
     ///
     /// # Example
     /// ```rust
@@ -59,22 +425,27 @@ impl RNG {
     /// ```
     #[cfg(feature = "std")]
     pub fn from_time() -> Self {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards")
-            .as_nanos();
-        let pid = process::id() as u128;
-        let seed = now ^ (pid << 32);
-        Self {
-            seed: seed as u64,
-            algorithm: Algorithm::Lcg,
-        }
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        let seed = unknown_wasm_seed();
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        let seed = {
+            let now = match SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
+                Ok(since) => since.as_nanos(),
+                Err(before) => before.duration().as_nanos() ^ fallback_entropy() as u128,
+            };
+            let pid = std::process::id() as u128;
+            (now ^ (pid << 32)) as u64
+        };
+        Self::from_generator(AnyAlgorithm(State::new(Algorithm::Lcg, seed)))
     }
 
-    /// Create a new RNG seeded from the current system entropy
+    /// Create a new RNG seeded from the operating system's CSPRNG
     ///
-    /// Only available with the `std` feature, additionally this is a beta feature.
-    /// This method uses operating system randomness and may not be available on all platforms.
+    /// Unlike [`RNG::from_time`], the seed cannot be guessed from the start time,
+    /// and processes started in the same instant get unrelated seeds. Uses the
+    /// same OS interfaces as `from_os_entropy`, falling back to `/dev/urandom` on
+    /// other Unix systems. Panics if no entropy can be read. Only available with
+    /// the `std` feature.
     ///
     /// # Example
     /// ```rust
@@ -84,62 +455,465 @@ impl RNG {
     #[cfg(feature = "std")]
     pub fn from_entropy() -> Self {
         let mut buf = [0u8; 8];
+        if !entropy::fill_os_entropy(&mut buf) && !read_urandom(&mut buf) {
+            panic!("{}", RngError::EntropyUnavailable)
+        }
+        Self::new(u64::from_le_bytes(buf))
+    }
 
-        #[cfg(unix)]
-        {
-            use std::fs::File;
-            use std::io::Read;
-
-            let mut f = File::open("/dev/urandom")
-                .expect("failed to open /dev/urandom");
-            f.read_exact(&mut buf)
-                .expect("failed to read from /dev/urandom");
-        }
-
-        #[cfg(windows)]
-        {
-            use core::ffi::c_void;
-
-            #[link(name = "bcrypt")]
-            extern "system" {
-                fn BCryptGenRandom(
-                    hAlgorithm: *mut c_void,
-                    pbBuffer: *mut u8,
-                    cbBuffer: u32,
-                    dwFlags: u32,
-                ) -> i32;
-            }
+    /// Set the RNG algorithm
+    ///
+    /// The new algorithm is seeded from the current state, so switching right after
+    /// construction gives the same sequence as seeding that algorithm directly.
+    /// Selecting the algorithm that is already active leaves the state untouched.
+    pub fn set_algorithm(&mut self, algorithm: Algorithm) {
+        if self.state.0.algorithm() != algorithm {
+            self.state = AnyAlgorithm(State::new(algorithm, self.state.0.seed_word()));
+            self.cached_u32 = None;
+        }
+    }
 
-            const BCRYPT_USE_SYSTEM_PREFERRED_RNG: u32 = 0x00000002;
+    /// Restart the selected algorithm from `seed`
+    ///
+    /// Gives the same sequence as `RNG::with_algorithm(seed, algorithm)` with the
+    /// current algorithm. A stream selected at construction is not kept.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::{Algorithm, RNG};
+    /// let mut rng = RNG::with_algorithm(1, Algorithm::Sfc64);
+    /// rng.next();
+    /// rng.reseed(42);
+    /// assert_eq!(rng.next(), RNG::with_algorithm(42, Algorithm::Sfc64).next());
+    /// ```
+    pub fn reseed(&mut self, seed: u64) {
+        self.state = AnyAlgorithm(State::new(self.state.0.algorithm(), seed));
+        self.cached_u32 = None;
+    }
 
-            let status = unsafe {
-                BCryptGenRandom(
-                    core::ptr::null_mut(),
-                    buf.as_mut_ptr(),
-                    buf.len() as u32,
-                    BCRYPT_USE_SYSTEM_PREFERRED_RNG,
-                )
-            };
+    /// Derive an independent child generator with the same algorithm
+    ///
+    /// The child is seeded from the parent's next output passed through a
+    /// [`SeedSeq`], so it does not share or trail the parent's sequence.
+    /// ChaCha and the DRBGs instead take a full 256-bit key mixed from the
+    /// parent's next four outputs, so a child is as strong as its parent.
+    /// Forking the same way from the same parent state always gives the same
+    /// children, which lets recursive procedural generation hand each subtree
+    /// its own RNG.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut world = RNG::new(42);
+    /// let mut left = world.fork();
+    /// let mut right = world.fork();
+    /// assert_ne!(left.next(), right.next());
+    /// ```
+    pub fn fork(&mut self) -> Self {
+        let algorithm = self.state.0.algorithm();
+        #[cfg(any(feature = "crypto", feature = "drbg"))]
+        if let Some(state) = State::from_key(algorithm, self.fork_key()) {
+            return Self::from_generator(AnyAlgorithm(state));
+        }
+        let seed = self.fork_seed();
+        Self::with_algorithm(seed, algorithm)
+    }
 
-            if status != 0 {
-                panic!("BCryptGenRandom failed");
+    /// Derive the generator for worker or task `worker_index` without changing this one
+    ///
+    /// The result depends only on the current state, position included, and
+    /// the index, so parallel runs are reproducible however the work is
+    /// scheduled, and drawing from the parent moves every worker. ChaCha hashes
+    /// its full key and position with the index into the worker's 256-bit key
+    /// and puts worker `i` on stream `i`, and the DRBGs reseed from the same
+    /// kind of hash. Other algorithms with streams pick the worker's seed and
+    /// stream by hashing the whole state and index, so no worker lands on the
+    /// parent's own stream except by a 2^-64 chance. Xoshiro-class
+    /// algorithms give index `i` the state [`RNG::jump`]ed `i + 1` times, a
+    /// non-overlapping slice of the sequence, in time linear in the index. Any
+    /// other algorithm is seeded from the state and index mixed through a
+    /// [`SeedSeq`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::{Algorithm, RNG};
+    /// let base = RNG::with_algorithm(42, Algorithm::Philox4x32);
+    /// let mut results = [0u64; 4];
+    /// for (index, result) in results.iter_mut().enumerate() {
+    ///     *result = base.stream_for(index as u64).next();
+    /// }
+    /// assert_eq!(results[2], base.stream_for(2).next());
+    /// ```
+    pub fn stream_for(&self, worker_index: u64) -> Self {
+        let algorithm = self.state.0.algorithm();
+        let mut state = self.state.0.clone();
+        #[cfg(any(feature = "crypto", feature = "drbg"))]
+        if state.rekey_worker(worker_index) {
+            return Self::from_generator(AnyAlgorithm(state));
+        }
+        // Hash the whole state, so the position counts as well as the key
+        let mut mixed = SeedSeq::new();
+        self.state
+            .0
+            .encode(&mut |piece| mixed = core::mem::take(&mut mixed).add_bytes(piece));
+        let mixed = mixed.add_u64(worker_index);
+        let mut words = [0u64; 2];
+        mixed.fill(&mut words);
+        if let Some(worker) = State::with_stream(algorithm, words[0], words[1]) {
+            return Self::from_generator(AnyAlgorithm(worker));
+        }
+        if state.jump(false) {
+            for _ in 0..worker_index {
+                state.jump(false);
+            }
+            return Self::from_generator(AnyAlgorithm(state));
+        }
+        Self::with_algorithm(mixed.seed_u64(), algorithm)
+    }
+
+    /// Fill `dest` with random values on every core, reproducibly (requires `std` feature)
+    ///
+    /// `dest` is split into blocks of 65536 values, and block `i` is filled
+    /// from [`RNG::stream_for`]`(i)`. The result depends only on the current
+    /// state, not on the number of threads or how the blocks are scheduled.
+    /// This generator is not advanced.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let rng = RNG::new(42);
+    /// let mut samples = vec![0.0f64; 1_000_000];
+    /// rng.par_fill(&mut samples);
+    /// let mut again = vec![0.0f64; 1_000_000];
+    /// rng.par_fill(&mut again);
+    /// assert_eq!(samples, again);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn par_fill<T: Random + Send>(&self, dest: &mut [T]) {
+        const BLOCK_LEN: usize = 1 << 16;
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let blocks = std::sync::Mutex::new(dest.chunks_mut(BLOCK_LEN).enumerate());
+        std::thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| {
+                    loop {
+                        let next = blocks.lock().unwrap().next();
+                        let Some((index, block)) = next else { break };
+                        self.stream_for(index as u64).fill(block);
+                    }
+                });
             }
+        });
+    }
+
+    /// Absorb fresh entropy, such as input timings or sensor noise, into the state
+    ///
+    /// Cryptographic algorithms keep their full key: the DRBGs run their
+    /// SP 800-90A reseed with `entropy` as the entropy input, and ChaCha hashes
+    /// its current key and position with `entropy` into a new key. DRBG input
+    /// shorter than 32 bytes is conditioned to 32 bytes with SHA-256 first,
+    /// which satisfies the reseed but is no stronger than the input. For the other
+    /// algorithms, the current state and `entropy` are mixed through a
+    /// [`SeedSeq`] and the result reseeds the selected algorithm. Either way,
+    /// weak or repeated entropy never makes the generator more predictable than
+    /// it was.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::new(42);
+    /// rng.mix_entropy(&[17, 250, 3]);
+    /// let value = rng.next();
+    /// println!("{}", value);
+    /// ```
+    pub fn mix_entropy(&mut self, entropy: &[u8]) {
+        #[cfg(any(feature = "crypto", feature = "drbg"))]
+        if self.state.0.rekey(entropy) {
+            self.cached_u32 = None;
+            return;
+        }
+        let seed = SeedSeq::new()
+            .add_u64(self.state.0.seed_word())
+            .add_bytes(entropy)
+            .seed_u64();
+        self.reseed(seed);
+    }
+
+    /// Jump to the output at `index` of a counter-based algorithm's stream
+    ///
+    /// Counter-based algorithms compute each output from its position, so this takes
+    /// constant time. Panics if the selected algorithm is not counter-based.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::{Algorithm, RNG};
+    /// let mut rng = RNG::with_algorithm(42, Algorithm::Philox4x32);
+    /// rng.seek(1_000_000);
+    /// let value = rng.next();
+    /// println!("{}", value);
+    /// ```
+    pub fn seek(&mut self, index: u64) {
+        self.cached_u32 = None;
+        if !self.state.0.seek(index) {
+            panic!("Algorithm is not counter-based")
+        }
+    }
+
+    /// Skip the next `delta` outputs in O(log delta) time
+    ///
+    /// Supported by the LCG-class algorithms: `Lcg`, `Pcg`, `Pcg64`, `Mcg128` and
+    /// `SplitMix64`. Panics if the selected algorithm cannot advance.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut stepped = RNG::new(42);
+    /// let mut skipped = RNG::new(42);
+    /// for _ in 0..1_000 {
+    ///     stepped.next();
+    /// }
+    /// skipped.advance(1_000);
+    /// assert_eq!(stepped.next(), skipped.next());
+    /// ```
+    pub fn advance(&mut self, delta: u64) {
+        self.cached_u32 = None;
+        if !self.state.0.advance(delta) {
+            panic!("Algorithm does not support advance")
+        }
+    }
+
+    /// Step an invertible algorithm back by one output and return that output
+    ///
+    /// The returned value is the one [`RNG::next`] produced last, and the next
+    /// call to `next` produces it again. Supported by `Lcg`, `Pcg`, `Pcg64`,
+    /// `Mcg128` and `SplitMix64`. Panics if the selected algorithm is not invertible.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::new(42);
+    /// let value = rng.next();
+    /// assert_eq!(rng.previous(), value);
+    /// assert_eq!(rng.next(), value);
+    /// ```
+    pub fn previous(&mut self) -> u64 {
+        self.cached_u32 = None;
+        if !self.state.0.previous() {
+            panic!("Algorithm is not invertible")
+        }
+        self.state.0.clone().next_u64()
+    }
+
+    /// Advance a xoshiro-class algorithm by a fixed, very large number of outputs
+    ///
+    /// Jumps 2^128 outputs for `Xoshiro256PlusPlus` and 2^64 for `Xoroshiro128Plus`.
+    /// Jumping once more for each worker hands every worker its own non-overlapping
+    /// slice of the sequence. Panics if the selected algorithm does not support jumps.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::{Algorithm, RNG};
+    /// let mut main = RNG::with_algorithm(42, Algorithm::Xoshiro256PlusPlus);
+    /// let mut worker = RNG::with_algorithm(42, Algorithm::Xoshiro256PlusPlus);
+    /// worker.jump();
+    /// assert_ne!(main.next(), worker.next());
+    /// ```
+    pub fn jump(&mut self) {
+        self.cached_u32 = None;
+        if !self.state.0.jump(false) {
+            panic!("Algorithm does not support jumps")
+        }
+    }
+
+    /// Advance a xoshiro-class algorithm by an even larger number of outputs than [`RNG::jump`]
+    ///
+    /// Jumps 2^192 outputs for `Xoshiro256PlusPlus` and 2^96 for `Xoroshiro128Plus`,
+    /// so each long jump can start a group of workers that then split it with
+    /// [`RNG::jump`]. Panics if the selected algorithm does not support jumps.
+    pub fn long_jump(&mut self) {
+        self.cached_u32 = None;
+        if !self.state.0.jump(true) {
+            panic!("Algorithm does not support jumps")
         }
+    }
 
-        #[cfg(not(any(unix, windows)))]
-        compile_error!("from_entropy is not supported on this platform");
+    /// Instantiate a NIST SP 800-90A DRBG from entropy input, a nonce and a personalization string
+    ///
+    /// `entropy_input` must hold at least 32 bytes, matching the 256-bit security
+    /// strength of the DRBG algorithms. Panics if `algorithm` is not a DRBG.
+    /// Only available with the `drbg` feature.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::{Algorithm, RNG};
+    /// let entropy = [7u8; 32];
+    /// let mut rng = RNG::instantiate_drbg(Algorithm::CtrDrbg, &entropy, b"nonce", b"my app");
+    /// let value = rng.next();
+    /// println!("{}", value);
+    /// ```
+    #[cfg(feature = "drbg")]
+    pub fn instantiate_drbg(
+        algorithm: Algorithm,
+        entropy_input: &[u8],
+        nonce: &[u8],
+        personalization: &[u8],
+    ) -> Self {
+        if entropy_input.len() < algorithms::MIN_ENTROPY_LEN {
+            panic!("entropy input must be at least 32 bytes")
+        }
+        match State::instantiate_drbg(algorithm, entropy_input, nonce, personalization) {
+            Some(state) => Self::from_generator(AnyAlgorithm(state)),
+            None => panic!("Algorithm is not a DRBG"),
+        }
+    }
 
+    /// Reseed the DRBG with fresh entropy input and optional additional input
+    ///
+    /// Panics if the selected algorithm is not a DRBG or `entropy_input` is shorter
+    /// than 32 bytes. Only available with the `drbg` feature.
+    #[cfg(feature = "drbg")]
+    pub fn reseed_drbg(&mut self, entropy_input: &[u8], additional_input: &[u8]) {
+        self.cached_u32 = None;
+        if entropy_input.len() < algorithms::MIN_ENTROPY_LEN {
+            panic!("entropy input must be at least 32 bytes")
+        }
+        if !self.state.0.reseed_drbg(entropy_input, additional_input) {
+            panic!("Algorithm is not a DRBG")
+        }
+    }
+
+    /// Fill `output` with a single DRBG generate request, mixing in `additional_input`
+    ///
+    /// A request may return at most 65536 bytes. Panics if the selected algorithm is
+    /// not a DRBG or `output` is too long. Only available with the `drbg` feature.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::{Algorithm, RNG};
+    /// let mut rng = RNG::instantiate_drbg(Algorithm::CtrDrbg, &[7u8; 32], b"nonce", b"");
+    /// let mut key = [0u8; 32];
+    /// rng.generate_drbg(&mut key, b"session 1");
+    /// ```
+    #[cfg(feature = "drbg")]
+    pub fn generate_drbg(&mut self, output: &mut [u8], additional_input: &[u8]) {
+        if output.len() > 1 << 16 {
+            panic!("DRBG request too large")
+        }
+        if !self.state.0.generate_drbg(output, additional_input) {
+            panic!("Algorithm is not a DRBG")
+        }
+    }
+}
+
+impl<A: SeedableAlgorithm> RNG<A> {
+    /// Create a new RNG with the algorithm fixed at compile time
+    ///
+    /// Each call goes straight to `A`, with no `match` on the selected algorithm.
+    /// The output is the same as [`RNG::with_algorithm`] with the matching
+    /// [`Algorithm`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::{RNG, Xoshiro256PlusPlus};
+    /// let mut rng = RNG::<Xoshiro256PlusPlus>::from_seed(42);
+    /// let value = rng.next();
+    /// println!("{}", value);
+    /// ```
+    pub fn from_seed(seed: u64) -> Self {
         Self {
-            seed: u64::from_le_bytes(buf),
-            algorithm: Algorithm::Lcg,
+            state: A::from_seed(seed),
+            cached_u32: None,
         }
     }
 
+    /// Create a new RNG from 32 seed bytes, such as a hash, key material or
+    /// bytes received over the network
+    ///
+    /// The bytes are read little-endian, so the same seed gives the same
+    /// sequence on every platform. See [`SeedableAlgorithm::from_seed_bytes`]
+    /// for how each generator uses them.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::{RNG, Xoshiro256PlusPlus};
+    /// let seed = [7u8; 32];
+    /// let mut rng = RNG::<Xoshiro256PlusPlus>::from_seed_bytes(seed);
+    /// let value = rng.next();
+    /// println!("{}", value);
+    /// ```
+    pub fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        Self::from_generator(A::from_seed_bytes(seed))
+    }
 
+    /// Create a new RNG from the mixed inputs of a [`SeedSeq`]
+    ///
+    /// Shorthand for `from_seed_bytes(seq.seed_bytes())`.
+    pub fn from_seed_seq(seq: &SeedSeq) -> Self {
+        Self::from_seed_bytes(seq.seed_bytes())
+    }
 
-    /// Set the RNG algorithm (LCG or PCG)
-    pub fn set_algorithm(&mut self, algorithm: Algorithm) {
-        self.algorithm = algorithm;
+    /// Derive an independent child generator, see [`RNG::fork`]
+    ///
+    /// The child is built with [`RNG::from_seed_bytes`] from the parent's next
+    /// four outputs mixed through a [`SeedSeq`], so generators that take a
+    /// 256-bit seed, such as ChaCha, get their full key.
+    pub fn fork(&mut self) -> Self {
+        let key = self.fork_key();
+        Self::from_seed_bytes(key)
+    }
+}
+
+impl<F: FnMut() -> u64> RNG<FromFn<F>> {
+    /// Create a new RNG that draws each value by calling `next_u64`
+    ///
+    /// This drives the helpers from a generator owned by another crate, such
+    /// as an OS or hardware source, without a wrapper type.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    ///
+    /// let mut external = 0x9E37_79B9_7F4A_7C15u64;
+    /// let mut rng = RNG::from_fn(|| {
+    ///     external ^= external << 7;
+    ///     external ^= external >> 9;
+    ///     external
+    /// });
+    /// let choice = rng.pick_random(&["red", "green", "blue"]);
+    /// println!("{:?}", choice);
+    /// ```
+    pub const fn from_fn(next_u64: F) -> Self {
+        Self::from_generator(FromFn(next_u64))
+    }
+}
+
+impl<A: RngAlgorithm> RNG<A> {
+    /// Create a new RNG that draws its values from `generator`
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::{RNG, RngAlgorithm};
+    ///
+    /// struct Xorshift(u64);
+    ///
+    /// impl RngAlgorithm for Xorshift {
+    ///     fn next_u64(&mut self) -> u64 {
+    ///         self.0 ^= self.0 << 13;
+    ///         self.0 ^= self.0 >> 7;
+    ///         self.0 ^= self.0 << 17;
+    ///         self.0
+    ///     }
+    /// }
+    ///
+    /// let mut rng = RNG::from_generator(Xorshift(88172645463325252));
+    /// let roll = rng.gen_range(1..=6);
+    /// println!("{}", roll);
+    /// ```
+    pub const fn from_generator(generator: A) -> Self {
+        Self {
+            state: generator,
+            cached_u32: None,
+        }
     }
 
     /// Advance the RNG and return the next random u64 value
@@ -151,48 +925,596 @@ impl RNG {
     /// let value = rng.next();
     /// println!("{}", value);
     /// ```
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> u64 {
-        self.seed = match self.algorithm {
-            Algorithm::Lcg => lcg(self.seed),
-            #[cfg(feature = "pcg")]
-            Algorithm::Pcg => pcg(self.seed),
-        };
-        self.seed
+        self.state.next_u64()
+    }
+
+    /// Endless iterator of [`RNG::next`] outputs, for use with `take`, `map`
+    /// and `collect`
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::new(42);
+    /// let ids: Vec<u64> = rng.iter().take(4).collect();
+    /// assert_eq!(ids.len(), 4);
+    /// ```
+    pub fn iter(&mut self) -> Iter<'_, A> {
+        Iter { rng: self }
+    }
+
+    /// Endless iterator of values in `range`, see [`RNG::gen_range`]
+    ///
+    /// The bounds are checked once, and the values match repeated
+    /// `gen_range` calls. Panics if the range is empty.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::new(42);
+    /// let total: u32 = rng.iter_range(1..=6).take(3).sum();
+    /// assert!((3..=18).contains(&total));
+    /// ```
+    pub fn iter_range<T: SampleUniform, R: RangeBounds<T>>(
+        &mut self,
+        range: R,
+    ) -> IterRange<'_, A, T> {
+        let (low, high) =
+            T::inclusive_bounds(range).unwrap_or_else(|| panic!("{}", RngError::EmptyRange));
+        IterRange {
+            rng: self,
+            uniform: Uniform::new(low, high),
+        }
     }
 
-    /// Generate a random integer in the range [min, max] (inclusive)
+    /// Endless iterator of random values of any [`Random`] type, see [`RNG::random`]
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::new(42);
+    /// let samples: Vec<f64> = rng.sample_iter().take(100).collect();
+    /// let heads = rng.sample_iter::<bool>().take(10).filter(|&b| b).count();
+    /// println!("{} {}", samples[0], heads);
+    /// ```
+    pub fn sample_iter<T: Random>(&mut self) -> SampleIter<'_, A, T> {
+        SampleIter {
+            rng: self,
+            marker: PhantomData,
+        }
+    }
+
+    /// Return the next random u128 value, for example for IDs and keys
+    ///
+    /// Built-in algorithms join two consecutive outputs, the first in the low half.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::new(7);
+    /// let id = rng.next_u128();
+    /// println!("{:032x}", id);
+    /// ```
+    pub fn next_u128(&mut self) -> u128 {
+        self.state.next_u128()
+    }
+
+    /// Return the next random u32 value, using one 64-bit output for every two calls
+    ///
+    /// The first call returns the low half of a fresh output and keeps the high
+    /// half for the second. Calls to [`RNG::next`] in between do not use the kept
+    /// half.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::new(7);
+    /// let low = rng.next_u32();
+    /// let high = rng.next_u32();
+    /// println!("{} {}", low, high);
+    /// ```
+    pub fn next_u32(&mut self) -> u32 {
+        match self.cached_u32.take() {
+            Some(high) => high,
+            None => {
+                let value = self.next();
+                self.cached_u32 = Some((value >> 32) as u32);
+                value as u32
+            }
+        }
+    }
+
+    /// Fill `dest` with random bytes, for nonces, salts or fuzzing input
+    ///
+    /// Each output provides eight bytes in little-endian order, and a final
+    /// partial chunk takes the low bytes of one more output.
+    ///
+    /// To randomize a slice of plain-old-data structs, such as a vertex buffer,
+    /// in one pass, fill its byte view from `bytemuck::cast_slice_mut`. For
+    /// types that need valid values, like `bool` or `char`, use [`RNG::fill`].
     ///
     /// # Example
     /// ```rust
     /// use simple_rng::RNG;
     /// let mut rng = RNG::from_time();
-    /// let value = rng.gen_range(1, 10);
-    /// println!("{}", value);
+    /// let mut nonce = [0u8; 12];
+    /// rng.fill_bytes(&mut nonce);
+    /// println!("{:?}", nonce);
     /// ```
-    pub fn gen_range(&mut self, min: u64, max: u64) -> u64 {
-        if max <= min {
-            panic!("max must be greater than min")
+    pub fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let (chunks, tail) = dest.as_chunks_mut::<8>();
+        for chunk in chunks {
+            *chunk = self.next().to_le_bytes();
+        }
+        if !tail.is_empty() {
+            let len = tail.len();
+            tail.copy_from_slice(&self.next().to_le_bytes()[..len]);
         }
-        let range = max - min + 1;
-        (self.next() % range) + min
     }
 
-    /// Generate a random floating-point value in [0.0, 1.0)
-    pub fn gen_float(&mut self) -> f64 {
-        (self.next() as f64) / (u64::MAX as f64 + 1.0)
+    /// Generate a random integer in a range, such as `1..=10` or `0..n`
+    ///
+    /// Works for every integer type implementing [`SampleUniform`], from `u8`
+    /// to `u128` and `i8` to `i128`, with the type taken from the range. Both
+    /// `a..b` (end excluded) and `a..=b` (end included) work, as do the open
+    /// forms like `..=b` and `a..`. Every value in the range is exactly
+    /// equally likely, using Lemire's multiply-shift rejection method instead
+    /// of a biased modulo.
+    ///
+    /// Panics if the range is empty, like `5..5`; see [`RNG::try_gen_range`] for
+    /// a version that returns an error instead.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::from_time();
+    /// let value = rng.gen_range(1..=10);
+    /// let index = rng.gen_range(0..3usize);
+    /// println!("{} {}", value, index);
+    /// ```
+    pub fn gen_range<T: SampleUniform, R: RangeBounds<T>>(&mut self, range: R) -> T {
+        self.try_gen_range(range)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Like [`RNG::gen_range`], but returns [`RngError::EmptyRange`] instead of
+    /// panicking if the range is empty
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::{RNG, RngError};
+    /// let mut rng = RNG::new(42);
+    /// assert!(rng.try_gen_range(1..=6).is_ok());
+    /// assert_eq!(rng.try_gen_range(5..5), Err(RngError::EmptyRange));
+    /// ```
+    pub fn try_gen_range<T: SampleUniform, R: RangeBounds<T>>(
+        &mut self,
+        range: R,
+    ) -> Result<T, RngError> {
+        let (low, high) = T::inclusive_bounds(range).ok_or(RngError::EmptyRange)?;
+        Ok(T::offset(low, self.offset_in(T::span(low, high))))
+    }
+
+    /// Uniform residue in [0, `modulus`), for modular arithmetic and hashing
+    ///
+    /// Every residue is exactly equally likely, with no modulo bias: biased
+    /// draws are rejected and redrawn, which is rare unless `modulus` is close
+    /// to 2^64. The same as `gen_range(0..modulus)`. For moduli wider than 64
+    /// bits, see `gen_digits_below` (requires `alloc` feature). Panics if
+    /// `modulus` is 0.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::new(42);
+    /// let p = 0xFFFF_FFFF_0000_0001;
+    /// let residue = rng.gen_below(p);
+    /// assert!(residue < p);
+    /// ```
+    pub fn gen_below(&mut self, modulus: u64) -> u64 {
+        if modulus == 0 {
+            panic!("{}", RngError::EmptyRange);
+        }
+        self.below(modulus)
+    }
+
+    // Seed for a child generator, mixed so the child does not trail the parent
+    fn fork_seed(&mut self) -> u64 {
+        SeedSeq::new().add_u64(self.next()).seed_u64()
+    }
+
+    // 32 seed bytes for a child generator, from four outputs mixed the same way
+    fn fork_key(&mut self) -> [u8; 32] {
+        let seq = (0..4).fold(SeedSeq::new(), |seq, _| seq.add_u64(self.next()));
+        seq.seed_bytes()
+    }
+
+    // Uniform value in [0, span], using a single output when the span fits in
+    // 64 bits; wider spans mask a u128 draw down to the span's bit length and
+    // redraw values past it, fewer than two draws on average
+    pub(crate) fn offset_in(&mut self, span: u128) -> u128 {
+        if span < u64::MAX as u128 {
+            self.below(span as u64 + 1) as u128
+        } else if span == u64::MAX as u128 {
+            self.next() as u128
+        } else {
+            let mask = u128::MAX >> span.leading_zeros();
+            loop {
+                let value = self.next_u128() & mask;
+                if value <= span {
+                    return value;
+                }
+            }
+        }
+    }
+
+    // Uniform value in [0, bound) by Lemire's method: take the high word of
+    // `next() * bound`, rejecting the few low words that would bias it
+    fn below(&mut self, bound: u64) -> u64 {
+        let mut product = self.next() as u128 * bound as u128;
+        if (product as u64) < bound {
+            let threshold = bound.wrapping_neg() % bound;
+            while (product as u64) < threshold {
+                product = self.next() as u128 * bound as u128;
+            }
+        }
+        (product >> 64) as u64
+    }
+
+    /// Generate a random floating-point value in [0.0, 1.0)
+    ///
+    /// Uses the top 53 bits of an output, the precision of an `f64`, so every
+    /// multiple of 2^-53 in the range is equally likely and 1.0 is never
+    /// returned.
+    pub fn gen_float(&mut self) -> f64 {
+        (self.next() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Generate a random floating-point value in the open interval (0.0, 1.0)
+    ///
+    /// Never returns 0.0, so it is safe to take `ln` of the result. Values are
+    /// the midpoints `(k + 0.5) * 2^-52` of 52-bit steps.
+    pub fn gen_float_open01(&mut self) -> f64 {
+        ((self.next() >> 12) as f64 + 0.5) * (1.0 / (1u64 << 52) as f64)
+    }
+
+    /// Generate a random floating-point value in the closed interval [0.0, 1.0]
+    ///
+    /// Like [`RNG::gen_float`] but with 53-bit steps scaled so the largest
+    /// output maps to exactly 1.0.
+    pub fn gen_float_closed01(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / ((1u64 << 53) - 1) as f64
+    }
+
+    /// Generate a random floating-point value in the range [min, max)
+    ///
+    /// Values are spread evenly over the range, with any draw that rounding
+    /// would push onto `max` drawn again. Ranges wider than the type's `MAX`,
+    /// such as `-f64::MAX` to `f64::MAX`, are scaled in halves so they don't
+    /// overflow. Works for `f64` and `f32` through [`SampleFloat`], so generic
+    /// code can take either.
+    ///
+    /// Panics if either bound is infinite or NaN, or if `max` is not greater
+    /// than `min`; see [`RNG::try_gen_float_range`] to get an error instead.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::from_time();
+    /// let offset = rng.gen_float_range(-1.0, 1.0);
+    /// let angle: f32 = rng.gen_float_range(0.0, 360.0);
+    /// println!("{} {}", offset, angle);
+    /// ```
+    pub fn gen_float_range<T: SampleFloat>(&mut self, min: T, max: T) -> T {
+        self.try_gen_float_range(min, max)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Like [`RNG::gen_float_range`], but returns [`RngError::NonFiniteBound`] or
+    /// [`RngError::EmptyRange`] instead of panicking
+    pub fn try_gen_float_range<T: SampleFloat>(&mut self, min: T, max: T) -> Result<T, RngError> {
+        T::sample_range(self, min, max)
+    }
+
+    /// Generate a random single-precision value in [0.0, 1.0)
+    ///
+    /// Uses the top 24 bits of [`RNG::next_u32`], the precision of an `f32`, so
+    /// two values come from each 64-bit output.
+    pub fn gen_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 * (1.0 / (1u32 << 24) as f32)
+    }
+
+    /// Generate a random boolean value
+    ///
+    /// Uses the top bit of an output, since the low bits of the LCG repeat
+    /// with a short period (the lowest one simply alternates).
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::from_time();
+    /// let side = rng.gen_bool();
+    /// println!("{}", if side { "Heads" } else { "Tails" });
+    /// ```
+    pub fn gen_bool(&mut self) -> bool {
+        self.next() >> 63 == 1
+    }
+
+    /// Generate a boolean that is true with probability `p`
+    ///
+    /// Compares one output against `p` scaled to the full u64 range, so
+    /// `p = 0.0` is never true and `p = 1.0` always is. Panics if `p` is
+    /// outside [0.0, 1.0] or NaN; see [`RNG::try_gen_bool_p`] to get an error
+    /// instead.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::from_time();
+    /// let critical_hit = rng.gen_bool_p(0.3);
+    /// println!("{}", critical_hit);
+    /// ```
+    pub fn gen_bool_p(&mut self, p: f64) -> bool {
+        self.try_gen_bool_p(p)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Like [`RNG::gen_bool_p`], but returns [`RngError::InvalidProbability`]
+    /// instead of panicking
+    pub fn try_gen_bool_p(&mut self, p: f64) -> Result<bool, RngError> {
+        if !(0.0..=1.0).contains(&p) {
+            return Err(RngError::InvalidProbability);
+        }
+        if p == 1.0 {
+            return Ok(true);
+        }
+        Ok(self.next() < (p * 18446744073709551616.0) as u64)
+    }
+
+    /// Generate a boolean that is true with probability `numerator / denominator`
+    ///
+    /// Uses only integer arithmetic, drawing an unbiased value below
+    /// `denominator` and comparing it with `numerator`, so the result is the
+    /// same on every platform. Panics if `denominator` is zero or smaller than
+    /// `numerator`; see [`RNG::try_gen_ratio`] to get an error instead.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::new(42);
+    /// let hit = rng.gen_ratio(3, 10);
+    /// println!("{}", hit);
+    /// ```
+    pub fn gen_ratio(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.try_gen_ratio(numerator, denominator)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Like [`RNG::gen_ratio`], but returns [`RngError::InvalidRatio`] instead
+    /// of panicking
+    pub fn try_gen_ratio(&mut self, numerator: u64, denominator: u64) -> Result<bool, RngError> {
+        if denominator == 0 || numerator > denominator {
+            return Err(RngError::InvalidRatio);
+        }
+        Ok(self.gen_range(0..denominator) < numerator)
+    }
+
+    /// Generate a boolean that is true with probability `1 / n`
+    ///
+    /// Shorthand for `gen_ratio(1, n)`, handy for drop rates. Panics if `n` is
+    /// zero; see [`RNG::try_one_in`] to get an error instead.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::from_time();
+    /// if rng.one_in(100) {
+    ///     println!("Rare drop!");
+    /// }
+    /// ```
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.try_one_in(n).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Like [`RNG::one_in`], but returns [`RngError::InvalidRatio`] instead of
+    /// panicking if `n` is zero
+    pub fn try_one_in(&mut self, n: u64) -> Result<bool, RngError> {
+        self.try_gen_ratio(1, n)
+    }
+
+    /// Generate a random value of any type implementing [`Random`]
+    ///
+    /// Integers cover their whole range, floats are in [0.0, 1.0) and `char`s
+    /// are any Unicode scalar value. Tuples and your own types implementing
+    /// [`Random`] work too.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::from_time();
+    /// let byte = rng.random::<u8>();
+    /// let offset: i16 = rng.random();
+    /// println!("{} {}", byte, offset);
+    /// ```
+    pub fn random<T: Random>(&mut self) -> T {
+        T::random(self)
+    }
+
+    /// Generate a fixed-size array of random values, such as `[u8; 32]`
+    ///
+    /// Elements are generated in order with [`RNG::random`], on the stack, so
+    /// this works without an allocator.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::from_time();
+    /// let key = rng.gen_array::<u8, 32>();
+    /// let color: [f32; 4] = rng.gen_array();
+    /// println!("{:?} {:?}", key, color);
+    /// ```
+    pub fn gen_array<T: Random, const N: usize>(&mut self) -> [T; N] {
+        core::array::from_fn(|_| self.random())
+    }
+
+    /// Generate a random u64 that is never zero, for IDs where zero is a sentinel
+    ///
+    /// A zero output is redrawn, so the other values are equally likely. Other
+    /// widths work through [`RNG::random`], such as `random::<NonZero<u32>>()`.
+    pub fn gen_nonzero_u64(&mut self) -> NonZeroU64 {
+        self.random()
+    }
+
+    /// Fill a slice with random values, one [`RNG::random`] per element
+    ///
+    /// Works for any [`Random`] element type, such as `f32` audio samples or
+    /// `u16` heightmaps. Byte buffers are filled eight times faster by
+    /// [`RNG::fill_bytes`], which uses every byte of each output.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::from_time();
+    /// let mut dither = [0.0f32; 256];
+    /// rng.fill(&mut dither);
+    /// println!("{:?}", dither[0]);
+    /// ```
+    pub fn fill<T: Random>(&mut self, dest: &mut [T]) {
+        for value in dest {
+            *value = self.random();
+        }
+    }
+
+    /// Generate a vector of `n` random values (requires `alloc` feature)
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::from_time();
+    /// let samples = rng.gen_vec::<f64>(100);
+    /// println!("{}", samples.len());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn gen_vec<T: Random>(&mut self, n: usize) -> Vec<T> {
+        (0..n).map(|_| self.random()).collect()
+    }
+
+    /// Generate a string of `len` random ASCII letters and digits (requires `alloc` feature)
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::from_time();
+    /// let token = rng.gen_string(16);
+    /// println!("{}", token);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn gen_string(&mut self, len: usize) -> String {
+        (0..len).map(|_| self.gen_ascii_alphanumeric()).collect()
+    }
+
+    /// Generate a random `Duration` in a range, for retry jitter and timers
+    ///
+    /// Every nanosecond in the range is equally likely. Like
+    /// [`RNG::gen_range`], which it calls, this is in `core` and works without
+    /// the `std` feature. Panics if the range is empty.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// use std::time::Duration;
+    /// let mut rng = RNG::from_time();
+    /// let backoff = rng.gen_duration(Duration::from_millis(100)..Duration::from_secs(2));
+    /// println!("{:?}", backoff);
+    /// ```
+    pub fn gen_duration<R: RangeBounds<Duration>>(&mut self, range: R) -> Duration {
+        self.gen_range(range)
+    }
+
+    /// Generate a random `SystemTime` in a range, for synthetic logs (requires `std` feature)
+    ///
+    /// Both ends must be given, as in `start..end` or `start..=end`. Every
+    /// nanosecond in the window is equally likely. Panics if the range is
+    /// empty or unbounded.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// use std::time::{Duration, SystemTime};
+    /// let mut rng = RNG::from_time();
+    /// let end = SystemTime::now();
+    /// let start = end - Duration::from_secs(24 * 60 * 60);
+    /// let logged_at = rng.gen_system_time(start..end);
+    /// println!("{:?}", logged_at);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn gen_system_time<R: RangeBounds<SystemTime>>(&mut self, range: R) -> SystemTime {
+        use core::ops::Bound;
+        let (start, low) = match range.start_bound() {
+            Bound::Included(&start) => (start, Bound::Included(Duration::ZERO)),
+            Bound::Excluded(&start) => (start, Bound::Excluded(Duration::ZERO)),
+            Bound::Unbounded => panic!("system time range must be bounded"),
+        };
+        let since_start = |end: SystemTime| {
+            end.duration_since(start)
+                .unwrap_or_else(|_| panic!("cannot sample empty range"))
+        };
+        let high = match range.end_bound() {
+            Bound::Included(&end) => Bound::Included(since_start(end)),
+            Bound::Excluded(&end) => Bound::Excluded(since_start(end)),
+            Bound::Unbounded => panic!("system time range must be bounded"),
+        };
+        start + self.gen_duration((low, high))
+    }
+
+    /// Generate a random `char`, any Unicode scalar value
+    ///
+    /// The surrogate code points U+D800 to U+DFFF are skipped, as they are not
+    /// valid `char`s, and every other code point is equally likely.
+    pub fn gen_char(&mut self) -> char {
+        self.gen_range(..)
+    }
+
+    /// Generate a random `char` in a range, such as `'a'..='z'`
+    ///
+    /// Like [`RNG::gen_range`], which it calls, skipping the surrogate code
+    /// points if the range spans them. Panics if the range is empty.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::from_time();
+    /// let letter = rng.gen_char_range('a'..='z');
+    /// println!("{}", letter);
+    /// ```
+    pub fn gen_char_range<R: RangeBounds<char>>(&mut self, range: R) -> char {
+        self.gen_range(range)
+    }
+
+    /// Generate a random ASCII letter or digit, `A`-`Z`, `a`-`z` or `0`-`9`
+    pub fn gen_ascii_alphanumeric(&mut self) -> char {
+        const ALPHANUMERIC: &[u8; 62] =
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+        ALPHANUMERIC[self.gen_range(0..ALPHANUMERIC.len())] as char
+    }
+
+    /// Generate a random printable ASCII character, from space to `~`
+    pub fn gen_ascii_printable(&mut self) -> char {
+        self.gen_range(' '..='~')
     }
 
-    /// Generate a random boolean value
+    /// Generate a random decimal digit character, `0` to `9`
     ///
     /// # Example
     /// ```rust
     /// use simple_rng::RNG;
     /// let mut rng = RNG::from_time();
-    /// let side = rng.gen_bool();
-    /// println!("{}", if side { "Heads" } else { "Tails" });
+    /// let pin: [char; 4] = core::array::from_fn(|_| rng.gen_digit());
+    /// println!("{:?}", pin);
     /// ```
-    pub fn gen_bool(&mut self) -> bool {
-        self.next() & 1 == 1
+    pub fn gen_digit(&mut self) -> char {
+        self.gen_range('0'..='9')
     }
 
     /// Generate a random unsigned integer of the specified bit size (8, 16, 32, 64)
@@ -201,15 +1523,17 @@ impl RNG {
     /// ```rust
     /// use simple_rng::RNG;
     /// let mut rng = RNG::from_time();
+    /// # #[allow(deprecated)]
     /// let number = rng.gen_unsigned(8);
     /// println!("This number is positive: {}!", number);
     /// ```
+    #[deprecated(note = "use `random::<u8>()` and the other integer types instead")]
     pub fn gen_unsigned(&mut self, size: u8) -> usize {
         match size {
             8 => self.next() as u8 as usize,
             16 => self.next() as u16 as usize,
             32 => self.next() as u32 as usize,
-            64 => self.next() as u64 as usize,
+            64 => self.next() as usize,
             _ => panic!("Unsupported size"),
         }
     }
@@ -220,9 +1544,11 @@ impl RNG {
     /// ```rust
     /// use simple_rng::RNG;
     /// let mut rng = RNG::from_time();
+    /// # #[allow(deprecated)]
     /// let number = rng.gen_signed(8);
     /// println!("This number is {}!", if number > 0 { "Positive" } else { "Negative" });
     /// ```
+    #[deprecated(note = "use `random::<i8>()` and the other integer types instead")]
     pub fn gen_signed(&mut self, size: u8) -> isize {
         match size {
             8 => self.next() as i8 as isize,
@@ -233,6 +1559,31 @@ impl RNG {
         }
     }
 
+    /// Shuffle a slice in place, with every order equally likely
+    ///
+    /// Uses the Fisher-Yates shuffle, drawing one [`RNG::gen_range`] per element.
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            slice.swap(i, self.gen_range(0..=i));
+        }
+    }
+
+    /// Return a shuffled copy of a slice, leaving the original as it is (requires `alloc` feature)
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::from_time();
+    /// let deck = rng.shuffled(&[1, 2, 3, 4, 5]);
+    /// println!("{:?}", deck);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn shuffled<T: Clone>(&mut self, slice: &[T]) -> Vec<T> {
+        let mut copy = slice.to_vec();
+        self.shuffle(&mut copy);
+        copy
+    }
+
     /// Pick a random element from a non-empty slice, or None if empty
     ///
     /// # Example
@@ -247,35 +1598,45 @@ impl RNG {
         if slice.is_empty() {
             None
         } else {
-            let idx = self.gen_range(0, slice.len() as u64 - 1) as usize;
+            let idx = self.gen_range(0..slice.len());
             slice.get(idx)
         }
     }
 }
 
-// Linear Congruential Generator (LCG) function
-fn lcg(seed: u64) -> u64 {
-    seed.wrapping_mul(6364136223846793005).wrapping_add(1)
+// `wasm32-unknown-unknown` has no clock or process id in `std`, both panic
+// there, so use the host's entropy through the `wasm` feature's import
+#[cfg(all(feature = "std", target_arch = "wasm32", target_os = "unknown"))]
+fn unknown_wasm_seed() -> u64 {
+    #[cfg(feature = "wasm")]
+    {
+        let mut buf = [0u8; 8];
+        if entropy::fill_os_entropy(&mut buf) {
+            return u64::from_le_bytes(buf);
+        }
+    }
+    fallback_entropy()
 }
 
-/// Permuted Congruential Generator (PCG-XSH-RR)
-///
-/// Uses LCG as the internal engine, then scrambles output for improved randomness.
-/// Only available with the `pcg` feature.
-///
-/// # Example
-/// ```rust
-/// use simple_rng::Algorithm::Pcg;
-/// use simple_rng::RNG;
-/// let mut rng = RNG::from_time();
-/// rng.set_algorithm(Pcg);
-/// ```
-#[cfg(feature = "pcg")]
-fn pcg(seed: u64) -> u64 {
-    let state = lcg(seed);
-    let xorshifted = ((state >> 18) ^ state) >> 27;
-    let rot = (state >> 59) as u32;
-    xorshifted.rotate_right(rot)
+// Read OS entropy through the `/dev/urandom` device, for Unix systems without
+// a direct interface in `entropy`
+#[cfg(feature = "std")]
+fn read_urandom(buf: &mut [u8]) -> bool {
+    use std::io::Read;
+    std::fs::File::open("/dev/urandom").is_ok_and(|mut file| file.read_exact(buf).is_ok())
+}
+
+// Entropy that needs no clock: a process-wide call counter and the address of a
+// stack local, which varies with ASLR, mixed through SplitMix64
+#[cfg(feature = "std")]
+fn fallback_entropy() -> u64 {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    let count = CALLS.fetch_add(1, Ordering::Relaxed) as u64;
+    let local = 0u8;
+    let address = &local as *const u8 as u64;
+    let [mixed] = algorithms::expand_seed(count ^ address.rotate_left(32));
+    mixed
 }
 
 #[cfg(test)]
@@ -286,9 +1647,9 @@ mod tests {
     /// next() should change the RNG's seed
     fn test_next_changes_seed() {
         let mut rng = RNG::new(123);
-        let old_seed = rng.seed;
+        let old_state = rng.state.clone();
         let _ = rng.next();
-        assert_ne!(rng.seed, old_seed);
+        assert_ne!(rng.state, old_state);
     }
 
     #[test]
@@ -298,17 +1659,17 @@ mod tests {
         use crate::Algorithm::Pcg;
         let mut rng = RNG::new(123);
         rng.set_algorithm(Pcg);
-        let old_seed = rng.seed;
+        let old_state = rng.state.clone();
         let _ = rng.next();
-        assert_ne!(rng.seed, old_seed);
+        assert_ne!(rng.state, old_state);
     }
 
     #[test]
     /// gen_range returns a value within the specified bounds
     fn test_gen_range_bounds() {
         let mut rng = RNG::new(42);
-        let val = rng.gen_range(10, 20);
-        assert!(val >= 10 && val <= 20);
+        let val = rng.gen_range(10..=20);
+        assert!((10..=20).contains(&val));
     }
 
     #[test]
@@ -318,8 +1679,8 @@ mod tests {
         use crate::Algorithm::Pcg;
         let mut rng = RNG::new(42);
         rng.set_algorithm(Pcg);
-        let val = rng.gen_range(10, 20);
-        assert!(val >= 10 && val <= 20);
+        let val = rng.gen_range(10..=20);
+        assert!((10..=20).contains(&val));
     }
 
     #[test]
@@ -356,6 +1717,718 @@ mod tests {
         }
         assert!(trues > 0 && falses > 0);
     }
+
+    #[test]
+    /// Switching algorithm after construction seeds it with the original seed
+    fn test_set_algorithm_xoshiro() {
+        let mut rng = RNG::new(42);
+        rng.set_algorithm(Algorithm::Xoshiro256PlusPlus);
+        assert_eq!(
+            rng.state,
+            AnyAlgorithm(State::new(Algorithm::Xoshiro256PlusPlus, 42))
+        );
+        let val = rng.gen_range(10..=20);
+        assert!((10..=20).contains(&val));
+    }
+
+    #[test]
+    /// with_algorithm is equivalent to switching right after construction
+    fn test_with_algorithm_xoroshiro() {
+        let mut switched = RNG::new(7);
+        switched.set_algorithm(Algorithm::Xoroshiro128Plus);
+        let mut direct = RNG::with_algorithm(7, Algorithm::Xoroshiro128Plus);
+        for _ in 0..10 {
+            assert_eq!(switched.next(), direct.next());
+        }
+    }
+
+    #[test]
+    /// Compile-time selection matches the runtime enum
+    fn test_from_seed_matches_with_algorithm() {
+        let mut fixed = RNG::<Xoshiro256PlusPlus>::from_seed(42);
+        let mut runtime = RNG::with_algorithm(42, Algorithm::Xoshiro256PlusPlus);
+        for _ in 0..10 {
+            assert_eq!(fixed.next(), runtime.next());
+        }
+        let mut fixed = RNG::<Philox4x32>::from_seed(7);
+        let mut runtime = RNG::with_algorithm(7, Algorithm::Philox4x32);
+        for _ in 0..10 {
+            assert_eq!(fixed.next(), runtime.next());
+        }
+    }
+
+    #[test]
+    /// Stream 0 is the default sequence and other streams differ from it
+    fn test_new_with_stream() {
+        let mut default = RNG::new(9);
+        let mut stream_zero = RNG::new_with_stream(9, 0);
+        let mut stream_one = RNG::new_with_stream(9, 1);
+        let first = default.next();
+        assert_eq!(stream_zero.next(), first);
+        assert_ne!(stream_one.next(), first);
+    }
+
+    #[test]
+    /// Seeking a Philox stream stays on that stream
+    fn test_philox_stream_seek() {
+        let mut a = RNG::with_algorithm_and_stream(1, 5, Algorithm::Philox4x32);
+        let mut b = RNG::with_algorithm_and_stream(1, 5, Algorithm::Philox4x32);
+        let mut other = RNG::with_algorithm(1, Algorithm::Philox4x32);
+        for _ in 0..3 {
+            a.next();
+        }
+        b.seek(3);
+        other.seek(3);
+        let value = a.next();
+        assert_eq!(b.next(), value);
+        assert_ne!(other.next(), value);
+    }
+
+    #[test]
+    #[should_panic(expected = "Algorithm does not support streams")]
+    /// Algorithms without streams are rejected
+    fn test_stream_requires_support() {
+        RNG::with_algorithm_and_stream(1, 2, Algorithm::Xoshiro256PlusPlus);
+    }
+
+    #[test]
+    #[should_panic(expected = "Algorithm does not support jumps")]
+    /// Only xoshiro-class algorithms can jump
+    fn test_jump_requires_support() {
+        RNG::new(1).jump();
+    }
+
+    #[test]
+    #[should_panic(expected = "Algorithm does not support advance")]
+    /// Only LCG-class algorithms can advance
+    fn test_advance_requires_support() {
+        RNG::with_algorithm(1, Algorithm::WyRand).advance(10);
+    }
+
+    #[test]
+    /// previous() rewinds through earlier outputs
+    fn test_previous_rewinds() {
+        let mut rng = RNG::with_algorithm(3, Algorithm::Mcg128);
+        let first = rng.next();
+        let second = rng.next();
+        assert_eq!(rng.previous(), second);
+        assert_eq!(rng.previous(), first);
+        assert_eq!(rng.next(), first);
+    }
+
+    #[test]
+    #[should_panic(expected = "Algorithm is not invertible")]
+    /// Only invertible algorithms can step back
+    fn test_previous_requires_support() {
+        RNG::with_algorithm(1, Algorithm::Sfc64).previous();
+    }
+
+    #[test]
+    /// next_u32 splits each output in two, low half first
+    fn test_next_u32_halves() {
+        let mut rng = RNG::with_algorithm(4, Algorithm::WyRand);
+        let mut reference = RNG::with_algorithm(4, Algorithm::WyRand);
+        let value = reference.next();
+        assert_eq!(rng.next_u32(), value as u32);
+        assert_eq!(rng.next_u32(), (value >> 32) as u32);
+        assert_eq!(rng.next_u32(), reference.next() as u32);
+    }
+
+    #[test]
+    /// Seeking drops the cached half
+    fn test_next_u32_after_seek() {
+        let mut rng = RNG::with_algorithm(4, Algorithm::Philox4x32);
+        let mut reference = RNG::with_algorithm(4, Algorithm::Philox4x32);
+        rng.next_u32();
+        rng.seek(10);
+        reference.seek(10);
+        assert_eq!(rng.next_u32(), reference.next() as u32);
+    }
+
+    #[test]
+    /// next_u128 joins two outputs, low half first
+    fn test_next_u128() {
+        let mut rng = RNG::new(11);
+        let mut reference = RNG::new(11);
+        let low = reference.next() as u128;
+        let high = reference.next() as u128;
+        assert_eq!(rng.next_u128(), (high << 64) | low);
+    }
+
+    // Replays a fixed list of outputs
+    struct Replay<'a>(&'a [u64]);
+
+    impl RngAlgorithm for Replay<'_> {
+        fn next_u64(&mut self) -> u64 {
+            let (first, rest) = self.0.split_first().unwrap();
+            self.0 = rest;
+            *first
+        }
+    }
+
+    #[test]
+    /// gen_range rejects the outputs that would bias the result
+    fn test_gen_range_rejects_biased_outputs() {
+        // 2^64 mod 3 == 1, so an output whose product has a low word of 0 is redrawn
+        let mut rng = RNG::from_generator(Replay(&[0, u64::MAX]));
+        assert_eq!(rng.gen_range(10..=12), 12);
+    }
+
+    #[test]
+    /// The full u64 range is accepted
+    fn test_gen_range_full_span() {
+        let mut rng = RNG::from_generator(Replay(&[12345]));
+        assert_eq!(rng.gen_range(..=u64::MAX), 12345);
+    }
+
+    #[test]
+    /// Ranges one short of the full u64 domain don't overflow either
+    fn test_gen_range_near_full_span() {
+        let mut rng = RNG::from_generator(Replay(&[u64::MAX, 1]));
+        assert_eq!(rng.gen_range(1..=u64::MAX), u64::MAX);
+        assert_eq!(rng.gen_range(0..u64::MAX), 0);
+    }
+
+    #[test]
+    /// gen_range excludes the end of a half-open range
+    fn test_gen_range_exclusive() {
+        let mut rng = RNG::new(3);
+        for _ in 0..100 {
+            assert!(rng.gen_range(0..3) < 3);
+        }
+        assert_eq!(rng.gen_range(5..=5), 5);
+        assert_eq!(rng.gen_range(u64::MAX..), u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot sample empty range")]
+    /// gen_range panics on an empty range
+    fn test_gen_range_empty() {
+        RNG::new(3).gen_range(5..5);
+    }
+
+    #[test]
+    /// gen_float maps the extreme outputs to 0.0 and the largest value below 1.0
+    fn test_gen_float_53_bits() {
+        let mut rng = RNG::from_generator(Replay(&[0, u64::MAX, 1 << 11]));
+        assert_eq!(rng.gen_float(), 0.0);
+        assert_eq!(rng.gen_float(), 1.0 - f64::EPSILON / 2.0);
+        assert_eq!(rng.gen_float(), f64::EPSILON / 2.0);
+    }
+
+    #[test]
+    /// gen_f32 takes 24 bits from each half of an output
+    fn test_gen_f32() {
+        let mut rng = RNG::from_generator(Replay(&[u64::MAX << 32, 1 << 8]));
+        assert_eq!(rng.gen_f32(), 0.0);
+        assert_eq!(rng.gen_f32(), 1.0 - f32::EPSILON / 2.0);
+        assert_eq!(rng.gen_f32(), f32::EPSILON / 2.0);
+    }
+
+    #[test]
+    /// gen_float_range stays in [min, max), even where rounding could reach max
+    fn test_gen_float_range() {
+        let mut rng = RNG::new(4);
+        for _ in 0..1000 {
+            let value = rng.gen_float_range(-1.0, 1.0);
+            assert!((-1.0..1.0).contains(&value));
+            let value = rng.gen_float_range(-f64::MAX, f64::MAX);
+            assert!(value.is_finite());
+        }
+        let mut top = RNG::from_generator(Replay(&[u64::MAX, 0]));
+        assert_eq!(top.gen_float_range(1.0, 1.0f64.next_up()), 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "range bounds must be finite")]
+    /// gen_float_range rejects non-finite bounds
+    fn test_gen_float_range_not_finite() {
+        RNG::new(4).gen_float_range(0.0, f64::INFINITY);
+    }
+
+    #[test]
+    /// The open and closed unit intervals handle the extreme outputs
+    fn test_gen_float_intervals() {
+        let mut rng = RNG::from_generator(Replay(&[0, u64::MAX, 0, u64::MAX]));
+        assert_eq!(rng.gen_float_open01(), f64::EPSILON / 2.0);
+        assert_eq!(rng.gen_float_open01(), 1.0 - f64::EPSILON / 2.0);
+        assert_eq!(rng.gen_float_closed01(), 0.0);
+        assert_eq!(rng.gen_float_closed01(), 1.0);
+    }
+
+    #[test]
+    /// gen_bool_p compares against the scaled probability
+    fn test_gen_bool_p() {
+        let mut rng = RNG::from_generator(Replay(&[0, 1 << 62, (1 << 62) - 1, u64::MAX]));
+        assert!(!rng.gen_bool_p(0.0));
+        assert!(!rng.gen_bool_p(0.25));
+        assert!(rng.gen_bool_p(0.25));
+        assert!(rng.gen_bool_p(1.0));
+        let mut rng = RNG::new(6);
+        let hits = (0..10000).filter(|_| rng.gen_bool_p(0.3)).count();
+        assert!((2800..3200).contains(&hits));
+    }
+
+    #[test]
+    #[should_panic(expected = "probability must be between 0 and 1")]
+    /// gen_bool_p rejects probabilities above 1
+    fn test_gen_bool_p_invalid() {
+        RNG::new(6).gen_bool_p(1.5);
+    }
+
+    #[test]
+    /// gen_ratio covers the certain and impossible cases and hits the ratio
+    fn test_gen_ratio() {
+        let mut rng = RNG::new(7);
+        assert!(rng.gen_ratio(5, 5));
+        assert!(!rng.gen_ratio(0, 5));
+        let hits = (0..10000).filter(|_| rng.gen_ratio(3, 10)).count();
+        assert!((2800..3200).contains(&hits));
+    }
+
+    #[test]
+    #[should_panic(expected = "ratio must be in [0, 1] with a non-zero denominator")]
+    /// gen_ratio rejects a zero denominator
+    fn test_gen_ratio_zero_denominator() {
+        RNG::new(7).gen_ratio(0, 0);
+    }
+
+    #[test]
+    /// one_in(1) is certain and one_in(n) hits about once in n
+    fn test_one_in() {
+        let mut rng = RNG::new(8);
+        assert!(rng.one_in(1));
+        let hits = (0..100000).filter(|_| rng.one_in(100)).count();
+        assert!((800..1200).contains(&hits));
+    }
+
+    #[test]
+    #[should_panic(expected = "ratio must be in [0, 1] with a non-zero denominator")]
+    /// one_in(0) has no chance to hit
+    fn test_one_in_zero() {
+        RNG::new(8).one_in(0);
+    }
+
+    #[test]
+    /// gen_bool reads the top bit, so LCG booleans don't just alternate
+    fn test_gen_bool_top_bit() {
+        let mut rng = RNG::from_generator(Replay(&[1, 1 << 63]));
+        assert!(!rng.gen_bool());
+        assert!(rng.gen_bool());
+        let mut rng = RNG::new(10);
+        let flips: [bool; 16] = core::array::from_fn(|_| rng.gen_bool());
+        assert!(flips.windows(3).any(|w| w[0] != w[2]));
+    }
+
+    #[test]
+    /// The ASCII helpers stay within their character classes
+    fn test_ascii_helpers() {
+        let mut rng = RNG::new(14);
+        let mut saw_digit = false;
+        for _ in 0..1000 {
+            let c = rng.gen_ascii_alphanumeric();
+            assert!(c.is_ascii_alphanumeric());
+            saw_digit |= c.is_ascii_digit();
+            let c = rng.gen_ascii_printable();
+            assert!(c == ' ' || c.is_ascii_graphic());
+            assert!(rng.gen_digit().is_ascii_digit());
+        }
+        assert!(saw_digit);
+    }
+
+    #[test]
+    /// gen_array fills each element in order
+    fn test_gen_array() {
+        let mut arrayed = RNG::new(15);
+        let mut single = RNG::new(15);
+        let values = arrayed.gen_array::<u16, 5>();
+        for value in values {
+            assert_eq!(value, single.random::<u16>());
+        }
+        assert_eq!(arrayed.random::<[u8; 3]>().len(), 3);
+    }
+
+    #[test]
+    /// fill_bytes lays out outputs little-endian and handles a partial tail
+    fn test_fill_bytes() {
+        let mut rng = RNG::from_generator(Replay(&[0x0807060504030201, 0x0C0B0A09]));
+        let mut bytes = [0u8; 11];
+        rng.fill_bytes(&mut bytes);
+        assert_eq!(bytes, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+    }
+
+    #[test]
+    /// fill generates the elements in order
+    fn test_fill() {
+        let mut filled = RNG::new(16);
+        let mut single = RNG::new(16);
+        let mut values = [0i32; 9];
+        filled.fill(&mut values);
+        for value in values {
+            assert_eq!(value, single.random::<i32>());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    /// gen_vec generates the requested number of values in order
+    fn test_gen_vec() {
+        let mut vectored = RNG::new(17);
+        let mut single = RNG::new(17);
+        let values = vectored.gen_vec::<u64>(20);
+        assert_eq!(values.len(), 20);
+        for value in values {
+            assert_eq!(value, single.next());
+        }
+    }
+
+    #[test]
+    /// shuffle keeps every element and moves some of them
+    fn test_shuffle_in_place() {
+        let mut rng = RNG::new(18);
+        let mut values = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        rng.shuffle(&mut values);
+        assert_ne!(values, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        values.sort();
+        assert_eq!(values, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    /// shuffled and gen_string build new collections without touching the input
+    fn test_alloc_helpers() {
+        let mut rng = RNG::new(19);
+        let original = [1, 2, 3, 4, 5];
+        let mut copy = rng.shuffled(&original);
+        copy.sort();
+        assert_eq!(copy, original);
+        let token = rng.gen_string(12);
+        assert_eq!(token.len(), 12);
+        assert!(token.chars().all(|c| c.is_ascii_alphanumeric()));
+    }
+
+    #[test]
+    /// Seed bytes fill xoshiro256++ directly and fold into the seed elsewhere
+    fn test_from_seed_bytes() {
+        let mut seed = [0u8; 32];
+        seed[0] = 1;
+        let mut direct = RNG::<Xoshiro256PlusPlus>::from_seed_bytes(seed);
+        // From the state [1, 0, 0, 0]: rotl(1, 23) + 1
+        assert_eq!(direct.next(), (1 << 23) + 1);
+
+        let mut folded = RNG::<Sfc64>::from_seed_bytes(seed);
+        let mut other = RNG::<Sfc64>::from_seed_bytes([0; 32]);
+        assert_ne!(folded.next(), other.next());
+    }
+
+    #[test]
+    /// String seeds use the FNV-1a hash of the string
+    fn test_from_str_seed() {
+        assert_eq!(
+            RNG::from_str_seed("").next(),
+            RNG::new(0xcbf29ce484222325).next()
+        );
+        assert_eq!(
+            RNG::from_str_seed("a").next(),
+            RNG::new(0xaf63dc4c8601ec8c).next()
+        );
+        assert_ne!(
+            RNG::from_str_seed("level-1").next(),
+            RNG::from_str_seed("level-2").next()
+        );
+    }
+
+    #[test]
+    /// The const constructors match the runtime ones
+    fn test_const_constructors() {
+        const SEEDED: RNG = RNG::new(23);
+        const STREAMED: RNG = RNG::new_with_stream(23, 4);
+        let (mut seeded, mut streamed) = (SEEDED, STREAMED);
+        assert_eq!(
+            seeded.next(),
+            RNG::with_algorithm(23, Algorithm::Lcg).next()
+        );
+        assert_eq!(
+            streamed.next(),
+            RNG::with_algorithm_and_stream(23, 4, Algorithm::Lcg).next()
+        );
+    }
+
+    #[test]
+    /// The try_ methods report bad input as errors without drawing
+    fn test_try_errors() {
+        let mut rng = RNG::new(22);
+        let (low, high) = (10, 1);
+        assert_eq!(rng.try_gen_range(low..=high), Err(RngError::EmptyRange));
+        assert_eq!(
+            rng.try_gen_float_range(0.0, f64::NAN),
+            Err(RngError::NonFiniteBound)
+        );
+        assert_eq!(rng.try_gen_float_range(1.0, 1.0), Err(RngError::EmptyRange));
+        assert_eq!(rng.try_gen_bool_p(-0.1), Err(RngError::InvalidProbability));
+        assert_eq!(rng.try_gen_ratio(4, 3), Err(RngError::InvalidRatio));
+        assert_eq!(rng.try_one_in(0), Err(RngError::InvalidRatio));
+        assert_eq!(rng.next(), RNG::new(22).next());
+        assert_eq!(rng.try_gen_range(0..1), Ok(0));
+        assert_eq!(rng.try_one_in(1), Ok(true));
+    }
+
+    #[test]
+    /// reseed keeps the algorithm and mix_entropy depends on state and input
+    fn test_reseed_and_mix_entropy() {
+        let mut rng = RNG::with_algorithm(1, Algorithm::Jsf64);
+        rng.next_u32();
+        rng.reseed(8);
+        let mut fresh = RNG::with_algorithm(8, Algorithm::Jsf64);
+        assert_eq!(rng.next_u32(), fresh.next_u32());
+
+        let mut a = RNG::new(5);
+        let mut b = RNG::new(5);
+        let mut c = RNG::new(6);
+        a.mix_entropy(b"noise");
+        b.mix_entropy(b"other");
+        c.mix_entropy(b"noise");
+        let outputs = [a.next(), b.next(), c.next()];
+        assert_ne!(outputs[0], outputs[1]);
+        assert_ne!(outputs[0], outputs[2]);
+        assert_eq!(a.state.0.algorithm(), Algorithm::Lcg);
+    }
+
+    #[test]
+    /// Forked children are reproducible and differ from the parent and each other
+    fn test_fork() {
+        let mut parent = RNG::with_algorithm(3, Algorithm::Lcg);
+        let mut twin = RNG::with_algorithm(3, Algorithm::Lcg);
+        let mut child = parent.fork();
+        let mut twin_child = twin.fork();
+        let mut sibling = parent.fork();
+        let value = child.next();
+        assert_eq!(value, twin_child.next());
+        assert_ne!(value, sibling.next());
+        assert_ne!(value, parent.next());
+        assert_eq!(child.state.0.algorithm(), Algorithm::Lcg);
+
+        let mut typed = RNG::<Xoshiro256PlusPlus>::from_seed(3);
+        let mut typed_child = typed.fork();
+        assert_ne!(typed_child.next(), typed.next());
+    }
+
+    #[test]
+    #[cfg(feature = "crypto")]
+    /// ChaCha children get a full 256-bit key instead of one expanded u64
+    fn test_fork_chacha_full_key() {
+        let mut parent = RNG::with_algorithm(3, Algorithm::ChaCha20);
+        let mut twin = RNG::with_algorithm(3, Algorithm::ChaCha20);
+        let child = parent.fork();
+        let outputs: [u64; 4] = core::array::from_fn(|_| twin.next());
+        let seq = outputs
+            .iter()
+            .fold(SeedSeq::new(), |seq, &word| seq.add_u64(word));
+        let key = seq.seed_bytes();
+        assert_eq!(
+            child.state.0,
+            State::ChaCha20(algorithms::ChaCha20::from_seed_bytes(key))
+        );
+        let narrow = SeedSeq::new().add_u64(outputs[0]).seed_u64();
+        assert_ne!(child.state.0, State::new(Algorithm::ChaCha20, narrow));
+        assert_eq!(parent.next(), twin.next());
+
+        let mut typed = RNG::<ChaCha20>::from_seed(3);
+        let typed_child = typed.fork();
+        assert_eq!(typed_child.state, ChaCha20::from_seed_bytes(key));
+    }
+
+    #[test]
+    /// Worker generators are reproducible, distinct, and leave the parent alone
+    fn test_stream_for() {
+        for algorithm in [
+            Algorithm::Lcg,
+            Algorithm::Xoshiro256PlusPlus,
+            Algorithm::Sfc64,
+        ] {
+            let base = RNG::with_algorithm(10, algorithm);
+            let outputs: [u64; 3] = core::array::from_fn(|i| base.stream_for(i as u64).next());
+            assert_ne!(outputs[0], outputs[1]);
+            assert_ne!(outputs[1], outputs[2]);
+            assert_eq!(outputs[1], base.stream_for(1).next());
+            assert_eq!(base.stream_for(1).state.0.algorithm(), algorithm);
+            assert_eq!(base.state, RNG::with_algorithm(10, algorithm).state);
+        }
+
+        let base = RNG::with_algorithm(10, Algorithm::Xoshiro256PlusPlus);
+        let mut jumped = RNG::with_algorithm(10, Algorithm::Xoshiro256PlusPlus);
+        jumped.jump();
+        jumped.jump();
+        assert_eq!(base.stream_for(1).next(), jumped.next());
+    }
+
+    #[test]
+    /// Drawing from the parent changes every worker, not just rekeying it
+    fn test_stream_for_follows_position() {
+        let check = |algorithm: Algorithm| {
+            let mut parent = RNG::with_algorithm(10, algorithm);
+            let before: [u64; 4] = core::array::from_fn(|i| parent.stream_for(i as u64).next());
+            parent.next();
+            for (index, first) in before.into_iter().enumerate() {
+                assert_eq!(
+                    parent.stream_for(index as u64).next(),
+                    parent.stream_for(index as u64).next()
+                );
+                assert_ne!(
+                    parent.stream_for(index as u64).next(),
+                    first,
+                    "{algorithm:?} {index}"
+                );
+            }
+        };
+        check(Algorithm::Philox4x32);
+        check(Algorithm::Threefry2x64);
+        check(Algorithm::Lcg);
+        check(Algorithm::Sfc64);
+        #[cfg(feature = "crypto")]
+        check(Algorithm::ChaCha20);
+        #[cfg(feature = "drbg")]
+        check(Algorithm::HmacDrbg);
+    }
+
+    #[test]
+    /// No worker repeats the parent's own stream, whichever stream it is on
+    fn test_stream_for_leaves_parent_stream() {
+        let check = |algorithm: Algorithm| {
+            for stream in [0, 3] {
+                let parent = RNG::with_algorithm_and_stream(10, stream, algorithm);
+                let mut continuation = RNG::with_algorithm_and_stream(10, stream, algorithm);
+                let ahead: [u64; 8] = core::array::from_fn(|_| continuation.next());
+                for index in 0..4 {
+                    let first = parent.stream_for(index).next();
+                    assert!(!ahead.contains(&first), "{algorithm:?} {stream} {index}");
+                }
+            }
+        };
+        check(Algorithm::Lcg);
+        check(Algorithm::Philox4x32);
+        check(Algorithm::Threefry2x64);
+        #[cfg(feature = "pcg")]
+        check(Algorithm::Pcg);
+        #[cfg(feature = "crypto")]
+        check(Algorithm::ChaCha20);
+    }
+
+    #[test]
+    /// The helpers draw from a closure
+    fn test_from_fn() {
+        let mut count = 0;
+        let mut rng = RNG::from_fn(|| {
+            count += 1;
+            count
+        });
+        assert_eq!(rng.next(), 1);
+        assert_eq!(rng.next_u32(), 2);
+        assert_eq!(rng.gen_range(10..=12), 10);
+    }
+
+    #[test]
+    /// gen_below matches gen_range over the same residues
+    fn test_gen_below() {
+        let mut rng = RNG::new(11);
+        let mut same = RNG::new(11);
+        for modulus in [1, 2, 7, 1 << 63, u64::MAX] {
+            let residue = rng.gen_below(modulus);
+            assert!(residue < modulus);
+            assert_eq!(residue, same.gen_range(0..modulus));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot sample empty range")]
+    /// There are no residues modulo 0
+    fn test_gen_below_zero() {
+        RNG::new(0).gen_below(0);
+    }
+
+    #[test]
+    #[cfg(feature = "crypto")]
+    /// mix_entropy rekeys ChaCha from its whole key, not a 64-bit fold of it
+    fn test_mix_entropy_keeps_chacha_key() {
+        let keyed = |key| RNG::from_generator(AnyAlgorithm(State::ChaCha20(key)));
+        let mut key = [0u8; 32];
+        let mut first = keyed(ChaCha20::from_seed_bytes(key));
+        key[31] = 1;
+        let mut second = keyed(ChaCha20::from_seed_bytes(key));
+        first.mix_entropy(b"event");
+        second.mix_entropy(b"event");
+        assert_ne!(first.next(), second.next());
+
+        let mut rng = RNG::with_algorithm(5, Algorithm::ChaCha20);
+        let mut same = RNG::with_algorithm(5, Algorithm::ChaCha20);
+        rng.mix_entropy(b"event");
+        same.state.0.rekey(b"event");
+        assert_eq!(rng.next(), same.next());
+    }
+
+    #[test]
+    #[cfg(feature = "drbg")]
+    /// mix_entropy reseeds an instantiated DRBG through SP 800-90A
+    fn test_mix_entropy_reseeds_drbg() {
+        for algorithm in [Algorithm::CtrDrbg, Algorithm::HmacDrbg, Algorithm::HashDrbg] {
+            let mut rng = RNG::instantiate_drbg(algorithm, &[7; 32], b"nonce", b"");
+            let mut reseeded = RNG::instantiate_drbg(algorithm, &[7; 32], b"nonce", b"");
+            rng.mix_entropy(&[9; 32]);
+            reseeded.reseed_drbg(&[9; 32], &[]);
+            assert_eq!(rng.next(), reseeded.next());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "drbg")]
+    /// Entropy shorter than 32 bytes is conditioned with SHA-256 before a DRBG reseed
+    fn test_mix_entropy_conditions_short_input() {
+        let mut hash = algorithms::sha256::Sha256::new();
+        hash.update(&[1, 2, 3]);
+        let conditioned = hash.finalize();
+        for algorithm in [Algorithm::CtrDrbg, Algorithm::HmacDrbg, Algorithm::HashDrbg] {
+            let mut rng = RNG::instantiate_drbg(algorithm, &[7; 32], b"nonce", b"");
+            let mut reseeded = RNG::instantiate_drbg(algorithm, &[7; 32], b"nonce", b"");
+            rng.mix_entropy(&[1, 2, 3]);
+            reseeded.reseed_drbg(&conditioned, &[]);
+            assert_eq!(rng.state, reseeded.state);
+        }
+    }
+
+    struct Sequence(u64);
+
+    impl RngAlgorithm for Sequence {
+        fn next_u64(&mut self) -> u64 {
+            self.0 += 1;
+            self.0
+        }
+    }
+
+    #[test]
+    /// The helpers draw from a user-supplied generator
+    fn test_from_generator() {
+        let mut rng = RNG::from_generator(Sequence(0));
+        assert_eq!(rng.next(), 1);
+        assert!(!rng.gen_bool());
+        assert_eq!(rng.gen_range(10..=12), 10);
+        assert_eq!(rng.pick_random(&[5, 6, 7]), Some(&5));
+    }
+
+    #[test]
+    #[should_panic(expected = "Algorithm is not counter-based")]
+    /// seek panics for algorithms that are not counter-based
+    fn test_seek_requires_counter_based() {
+        let mut rng = RNG::new(42);
+        rng.seek(10);
+    }
+
+    #[test]
+    #[cfg(feature = "drbg")]
+    #[should_panic(expected = "entropy input must be at least 32 bytes")]
+    /// DRBGs refuse to instantiate from less entropy than their security strength
+    fn test_instantiate_drbg_rejects_short_entropy() {
+        let _ = RNG::instantiate_drbg(Algorithm::CtrDrbg, &[0u8; 16], &[], &[]);
+    }
 }
 
 #[cfg(all(test, feature = "std"))]
@@ -363,13 +2436,74 @@ mod std_tests {
     use super::*;
     use std::vec;
 
+    #[test]
+    /// The clock-free fallback seed differs between calls
+    fn test_fallback_entropy() {
+        assert_ne!(fallback_entropy(), fallback_entropy());
+    }
+
+    #[test]
+    /// gen_system_time stays inside the window
+    fn test_gen_system_time() {
+        use std::time::Duration;
+        let mut rng = RNG::new(21);
+        let start = std::time::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let end = start + Duration::from_secs(3600);
+        for _ in 0..1000 {
+            let time = rng.gen_system_time(start..end);
+            assert!(time >= start && time < end);
+        }
+        assert_eq!(rng.gen_system_time(start..=start), start);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot sample empty range")]
+    /// gen_system_time panics when the window ends before it starts
+    fn test_gen_system_time_reversed() {
+        let now = SystemTime::now();
+        RNG::new(21).gen_system_time(now + std::time::Duration::from_secs(1)..now);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    /// Separate entropy seeds differ, through the OS interface and /dev/urandom alike
+    fn test_from_entropy() {
+        assert_ne!(RNG::from_entropy().next(), RNG::from_entropy().next());
+        let mut buf = [0u8; 16];
+        assert!(read_urandom(&mut buf));
+        assert_ne!(buf, [0; 16]);
+    }
+
+    #[test]
+    /// Each block of par_fill comes from the stream for its index
+    fn test_par_fill() {
+        let rng = RNG::with_algorithm(9, Algorithm::Xoshiro256PlusPlus);
+        let mut values = vec![0u32; (1 << 16) + 100];
+        rng.par_fill(&mut values);
+        let mut first = rng.stream_for(0);
+        assert!(values[..1 << 16].iter().all(|&v| v == first.random()));
+        let mut second = rng.stream_for(1);
+        assert!(values[1 << 16..].iter().all(|&v| v == second.random()));
+    }
+
+    #[test]
+    /// The first block of par_fill does not repeat the generator's own output
+    fn test_par_fill_leaves_parent_stream() {
+        let rng = RNG::with_algorithm(9, Algorithm::Lcg);
+        let mut values = [0u64; 64];
+        rng.par_fill(&mut values);
+        let mut continuation = RNG::with_algorithm(9, Algorithm::Lcg);
+        let ahead: [u64; 64] = core::array::from_fn(|_| continuation.next());
+        assert!(values.iter().all(|value| !ahead.contains(value)));
+    }
+
     #[test]
     /// Tests removing from a vector? Not sure why this is here might be legacy code ;)
     fn test_shuffle() {
         let mut rng = RNG::new(123);
         let mut v = vec![1, 2, 3, 4];
         while v.len() > 1 {
-            let idx = rng.gen_range(0, v.len() as u64 - 1) as usize;
+            let idx = rng.gen_range(0..v.len());
             v.remove(idx);
         }
         if !v.is_empty() {
@@ -387,7 +2521,7 @@ mod std_tests {
         rng.set_algorithm(Pcg);
         let mut v = vec![1, 2, 3, 4];
         while v.len() > 1 {
-            let idx = rng.gen_range(0, v.len() as u64 - 1) as usize;
+            let idx = rng.gen_range(0..v.len());
             v.remove(idx);
         }
         if !v.is_empty() {
@@ -0,0 +1,304 @@
+//! Probable-prime generation with Miller–Rabin, see [`RNG::gen_prime`].
+//!
+//! Numbers use the little-endian `u32` digits of [`RNG::gen_digits`]. Modular
+//! exponentiation uses Montgomery multiplication, so no division is needed
+//! after the setup.
+
+use crate::digits::{bit_len, compare, sub_assign, sub_small};
+use crate::{RNG, RngAlgorithm};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// Odd primes below 256, for trial division before Miller–Rabin
+const SMALL_PRIMES: [u32; 53] = [
+    3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179, 181, 191, 193,
+    197, 199, 211, 223, 227, 229, 233, 239, 241, 251,
+];
+
+impl<A: RngAlgorithm> RNG<A> {
+    /// Random probable prime of exactly `bits` bits, as little-endian `u32`
+    /// digits (requires `alloc` feature)
+    ///
+    /// Random odd candidates with the top bit set go through trial division
+    /// and then `rounds` Miller–Rabin rounds with random bases. A composite
+    /// passes each round with probability at most 1/4, and far less for random
+    /// candidates of cryptographic size. The primes are only as unpredictable
+    /// as the generator, so use a `crypto` algorithm for real keys. Panics if
+    /// `bits` is less than 2.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::new(42);
+    /// let prime = rng.gen_prime(128, 40);
+    /// assert_eq!(prime.len(), 4);
+    /// assert!(rng.is_probable_prime(&prime, 40));
+    /// ```
+    pub fn gen_prime(&mut self, bits: u32, rounds: u32) -> Vec<u32> {
+        assert!(bits >= 2, "a prime needs at least 2 bits");
+        let top = bits as usize - 1;
+        loop {
+            let mut candidate = self.gen_digits(bits);
+            candidate[top / 32] |= 1 << (top % 32);
+            candidate[0] |= 1;
+            if self.is_probable_prime(&candidate, rounds) {
+                return candidate;
+            }
+        }
+    }
+
+    /// Test whether `n`, as little-endian `u32` digits, is probably prime
+    /// (requires `alloc` feature)
+    ///
+    /// Numbers below 65536 are answered exactly by trial division. Larger ones
+    /// that pass it get `rounds` Miller–Rabin rounds with bases drawn from this
+    /// generator, and a composite survives each round with probability at most
+    /// 1/4.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::new(42);
+    /// // 2^61 - 1 is a Mersenne prime
+    /// assert!(rng.is_probable_prime(&[u32::MAX, (1 << 29) - 1], 20));
+    /// assert!(!rng.is_probable_prime(&[561], 20));
+    /// ```
+    pub fn is_probable_prime(&mut self, n: &[u32], rounds: u32) -> bool {
+        let n = &n[..bit_len(n).div_ceil(32) as usize];
+        match n {
+            [] | [1] => return false,
+            [2] => return true,
+            _ if n[0] & 1 == 0 => return false,
+            _ => {}
+        }
+        for prime in SMALL_PRIMES {
+            if remainder(n, prime) == 0 {
+                return n == [prime];
+            }
+        }
+        if let [small] = n
+            && *small < 1 << 16
+        {
+            return true;
+        }
+
+        // n - 1 = d * 2^shift with d odd
+        let mut n_minus_1 = n.to_vec();
+        n_minus_1[0] -= 1;
+        let shift = n_minus_1
+            .iter()
+            .position(|&digit| digit != 0)
+            .map_or(0, |index| {
+                index as u32 * 32 + n_minus_1[index].trailing_zeros()
+            });
+        let d = shift_right(&n_minus_1, shift);
+
+        let field = Montgomery::new(n);
+        let minus_one = sub(n, &field.one);
+        // Bases are uniform in [2, n - 2]
+        let mut span = n.to_vec();
+        sub_small(&mut span, 3);
+        let mut base = vec![0; n.len()];
+        'rounds: for _ in 0..rounds {
+            self.fill_digits_below(&mut base, &span);
+            add_small(&mut base, 2);
+            let mut x = field.pow(&field.to_montgomery(&base), &d);
+            if x == field.one || x == minus_one {
+                continue;
+            }
+            for _ in 1..shift {
+                x = field.mul(&x, &x);
+                if x == minus_one {
+                    continue 'rounds;
+                }
+            }
+            return false;
+        }
+        true
+    }
+}
+
+/// `n mod divisor`
+fn remainder(n: &[u32], divisor: u32) -> u32 {
+    n.iter().rev().fold(0, |rem, &digit| {
+        (((rem as u64) << 32 | digit as u64) % divisor as u64) as u32
+    })
+}
+
+/// `n >> shift`, keeping the length
+fn shift_right(n: &[u32], shift: u32) -> Vec<u32> {
+    let (words, bits) = ((shift / 32) as usize, shift % 32);
+    (0..n.len())
+        .map(|index| {
+            let low = n.get(index + words).copied().unwrap_or(0) as u64;
+            let high = n.get(index + words + 1).copied().unwrap_or(0) as u64;
+            ((high << 32 | low) >> bits) as u32
+        })
+        .collect()
+}
+
+/// `a - b` for `a >= b` of the same length
+fn sub(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut out = a.to_vec();
+    sub_assign(&mut out, b);
+    out
+}
+
+fn add_small(a: &mut [u32], value: u32) {
+    let mut carry = value;
+    for digit in a {
+        let (next, overflow) = digit.overflowing_add(carry);
+        *digit = next;
+        carry = overflow as u32;
+    }
+}
+
+/// Arithmetic modulo an odd `n` in Montgomery form, with R = 2^(32 * digits)
+struct Montgomery<'a> {
+    n: &'a [u32],
+    // -n^-1 mod 2^32
+    n_inv: u32,
+    // R mod n, which is 1 in Montgomery form
+    one: Vec<u32>,
+    // R^2 mod n, for converting into Montgomery form
+    r_squared: Vec<u32>,
+}
+
+impl<'a> Montgomery<'a> {
+    fn new(n: &'a [u32]) -> Self {
+        // Newton's iteration doubles the correct low bits each step
+        let mut inverse = 1u32;
+        for _ in 0..5 {
+            inverse = inverse.wrapping_mul(2u32.wrapping_sub(n[0].wrapping_mul(inverse)));
+        }
+
+        // Double 1 modulo n to reach R, then R^2
+        let mut value = vec![0; n.len()];
+        value[0] = 1;
+        let mut one = Vec::new();
+        for step in 0..64 * n.len() {
+            let mut carry = 0;
+            for digit in value.iter_mut() {
+                let next = (*digit << 1) | carry;
+                carry = *digit >> 31;
+                *digit = next;
+            }
+            if carry == 1 || compare(&value, n) != Ordering::Less {
+                sub_assign(&mut value, n);
+            }
+            if step + 1 == 32 * n.len() {
+                one = value.clone();
+            }
+        }
+        Self {
+            n,
+            n_inv: inverse.wrapping_neg(),
+            one,
+            r_squared: value,
+        }
+    }
+
+    /// `a * b / R mod n` for `a, b < n`, by coarsely integrated operand scanning
+    fn mul(&self, a: &[u32], b: &[u32]) -> Vec<u32> {
+        let len = self.n.len();
+        let mut t = vec![0u32; len + 2];
+        for &factor in b {
+            let mut carry = 0u64;
+            for (slot, &digit) in t.iter_mut().zip(a) {
+                let sum = *slot as u64 + digit as u64 * factor as u64 + carry;
+                *slot = sum as u32;
+                carry = sum >> 32;
+            }
+            let sum = t[len] as u64 + carry;
+            t[len] = sum as u32;
+            t[len + 1] = (sum >> 32) as u32;
+
+            // Add m * n to clear the low digit, then drop it
+            let m = t[0].wrapping_mul(self.n_inv);
+            let mut carry = (t[0] as u64 + m as u64 * self.n[0] as u64) >> 32;
+            for index in 1..len {
+                let sum = t[index] as u64 + m as u64 * self.n[index] as u64 + carry;
+                t[index - 1] = sum as u32;
+                carry = sum >> 32;
+            }
+            let sum = t[len] as u64 + carry;
+            t[len - 1] = sum as u32;
+            t[len] = t[len + 1] + (sum >> 32) as u32;
+            t[len + 1] = 0;
+        }
+        let overflow = t[len] != 0;
+        t.truncate(len);
+        if overflow || compare(&t, self.n) != Ordering::Less {
+            sub_assign(&mut t, self.n);
+        }
+        t
+    }
+
+    fn to_montgomery(&self, a: &[u32]) -> Vec<u32> {
+        self.mul(a, &self.r_squared)
+    }
+
+    /// `base ^ exponent` with `base` in Montgomery form, by square and multiply
+    fn pow(&self, base: &[u32], exponent: &[u32]) -> Vec<u32> {
+        let mut result = self.one.clone();
+        for bit in (0..bit_len(exponent)).rev() {
+            result = self.mul(&result, &result);
+            if exponent[bit as usize / 32] >> (bit % 32) & 1 == 1 {
+                result = self.mul(&result, base);
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Known primes pass and composites, including Carmichael numbers, fail
+    fn test_is_probable_prime() {
+        let mut rng = RNG::new(1);
+        for prime in [2, 3, 5, 251, 257, 65521, 4294967291] {
+            assert!(rng.is_probable_prime(&[prime], 20), "{prime}");
+        }
+        for composite in [0, 1, 4, 9, 561, 41041, 65535, 4294967295] {
+            assert!(!rng.is_probable_prime(&[composite], 20), "{composite}");
+        }
+        // 2^127 - 1, and 4294967291 * 4294967279
+        assert!(rng.is_probable_prime(&[u32::MAX, u32::MAX, u32::MAX, u32::MAX >> 1], 20));
+        assert!(!rng.is_probable_prime(&[85, 4294967274], 20));
+        // Leading zero digits are ignored
+        assert!(rng.is_probable_prime(&[65521, 0, 0], 20));
+    }
+
+    #[test]
+    /// Primes have exactly the requested bit length
+    fn test_gen_prime() {
+        let mut rng = RNG::new(2);
+        assert_eq!(rng.gen_prime(2, 10), [3]);
+        for bits in [17, 32, 33, 64, 100] {
+            let prime = rng.gen_prime(bits, 20);
+            assert_eq!(bit_len(&prime), bits);
+            assert!(rng.is_probable_prime(&prime, 20));
+        }
+    }
+
+    #[test]
+    /// Montgomery products match plain modular arithmetic
+    fn test_montgomery_mul() {
+        let n = 4294967291u64 * 4294967279;
+        let digits = [n as u32, (n >> 32) as u32];
+        let field = Montgomery::new(&digits);
+        let (a, b) = (0x1234_5678_9ABC_DEF0 % n, 0x0FED_CBA9_8765_4321 % n);
+        let to_digits = |v: u64| [v as u32, (v >> 32) as u32];
+        let product = field.mul(
+            &field.to_montgomery(&to_digits(a)),
+            &field.to_montgomery(&to_digits(b)),
+        );
+        let plain = (a as u128 * b as u128 % n as u128) as u64;
+        assert_eq!(product, field.to_montgomery(&to_digits(plain)));
+    }
+}
@@ -0,0 +1,197 @@
+//! Values of any primitive type, see [`Random`].
+
+use crate::{RNG, RngAlgorithm};
+use core::num::NonZero;
+
+/// A type that [`RNG::random`] can generate
+///
+/// Implemented for every primitive integer and its `NonZero` form, `bool`,
+/// `f32`, `f64` and `char`, and for arrays and tuples (of up to four elements)
+/// of `Random` types. Integers cover their whole range; floats are in
+/// [0.0, 1.0) and `char`s are any Unicode scalar value.
+///
+/// Implement it for your own types to generate them the same way:
+///
+/// ```rust
+/// use simple_rng::{RNG, Random, RngAlgorithm};
+///
+/// struct Vec3 {
+///     x: f32,
+///     y: f32,
+///     z: f32,
+/// }
+///
+/// impl Random for Vec3 {
+///     fn random<A: RngAlgorithm>(rng: &mut RNG<A>) -> Self {
+///         Vec3 {
+///             x: rng.random(),
+///             y: rng.random(),
+///             z: rng.random(),
+///         }
+///     }
+/// }
+///
+/// let mut rng = RNG::new(42);
+/// let point = rng.random::<Vec3>();
+/// println!("{} {} {}", point.x, point.y, point.z);
+/// ```
+pub trait Random {
+    /// Generate a random value from `rng`
+    fn random<A: RngAlgorithm>(rng: &mut RNG<A>) -> Self;
+}
+
+// Types up to 64 bits take the top bits of one output, which are the
+// strongest bits of the LCG
+macro_rules! impl_random {
+    ($($ty:ty),*) => {$(
+        impl Random for $ty {
+            fn random<A: RngAlgorithm>(rng: &mut RNG<A>) -> Self {
+                (rng.next() >> (64 - <$ty>::BITS)) as $ty
+            }
+        }
+    )*};
+}
+
+impl_random!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+// Non-zero integers redraw the rare zero, so the other values stay uniform
+macro_rules! impl_random_nonzero {
+    ($($ty:ty),*) => {$(
+        impl Random for NonZero<$ty> {
+            fn random<A: RngAlgorithm>(rng: &mut RNG<A>) -> Self {
+                loop {
+                    if let Some(value) = NonZero::new(rng.random::<$ty>()) {
+                        return value;
+                    }
+                }
+            }
+        }
+    )*};
+}
+
+impl_random_nonzero!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+impl Random for u128 {
+    fn random<A: RngAlgorithm>(rng: &mut RNG<A>) -> Self {
+        rng.next_u128()
+    }
+}
+
+impl Random for i128 {
+    fn random<A: RngAlgorithm>(rng: &mut RNG<A>) -> Self {
+        rng.next_u128() as i128
+    }
+}
+
+impl Random for bool {
+    fn random<A: RngAlgorithm>(rng: &mut RNG<A>) -> Self {
+        rng.gen_bool()
+    }
+}
+
+impl Random for f32 {
+    fn random<A: RngAlgorithm>(rng: &mut RNG<A>) -> Self {
+        rng.gen_f32()
+    }
+}
+
+impl Random for f64 {
+    fn random<A: RngAlgorithm>(rng: &mut RNG<A>) -> Self {
+        rng.gen_float()
+    }
+}
+
+// Tuples of random values, up to four elements, generated left to right
+macro_rules! impl_random_tuple {
+    ($(($($name:ident),+)),*) => {$(
+        impl<$($name: Random),+> Random for ($($name,)+) {
+            fn random<A: RngAlgorithm>(rng: &mut RNG<A>) -> Self {
+                ($($name::random(rng),)+)
+            }
+        }
+    )*};
+}
+
+impl_random_tuple!((T1), (T1, T2), (T1, T2, T3), (T1, T2, T3, T4));
+
+impl<T: Random, const N: usize> Random for [T; N] {
+    fn random<A: RngAlgorithm>(rng: &mut RNG<A>) -> Self {
+        core::array::from_fn(|_| T::random(rng))
+    }
+}
+
+impl Random for char {
+    fn random<A: RngAlgorithm>(rng: &mut RNG<A>) -> Self {
+        rng.gen_range(..)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RNG, RngAlgorithm};
+    use core::num::NonZero;
+
+    struct Constant(u64);
+
+    impl RngAlgorithm for Constant {
+        fn next_u64(&mut self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    /// Narrow integers take the top bits of the output
+    fn test_top_bits() {
+        let mut rng = RNG::from_generator(Constant(0xABCD_0000_0000_1234));
+        assert_eq!(rng.random::<u8>(), 0xAB);
+        assert_eq!(rng.random::<u16>(), 0xABCD);
+        assert_eq!(rng.random::<i8>(), 0xABu8 as i8);
+        assert_eq!(rng.random::<u64>(), 0xABCD_0000_0000_1234);
+    }
+
+    #[test]
+    /// Non-zero integers skip zero outputs
+    fn test_nonzero() {
+        struct Replay<'a>(&'a [u64]);
+
+        impl RngAlgorithm for Replay<'_> {
+            fn next_u64(&mut self) -> u64 {
+                let (first, rest) = self.0.split_first().unwrap();
+                self.0 = rest;
+                *first
+            }
+        }
+
+        let mut rng = RNG::from_generator(Replay(&[0, 0xFF, 7 << 56]));
+        assert_eq!(rng.random::<NonZero<u64>>().get(), 0xFF);
+        assert_eq!(rng.random::<NonZero<u8>>().get(), 7);
+    }
+
+    #[test]
+    /// Tuples draw their elements in order
+    fn test_tuple() {
+        let mut tupled = RNG::new(12);
+        let mut single = RNG::new(12);
+        let (a, b, c) = tupled.random::<(u32, bool, f64)>();
+        assert_eq!(a, single.random::<u32>());
+        assert_eq!(b, single.gen_bool());
+        assert_eq!(c, single.gen_float());
+    }
+
+    #[test]
+    /// Chars skip the surrogate range and reach both ends of Unicode
+    fn test_char() {
+        assert_eq!(RNG::from_generator(Constant(1)).random::<char>(), '\0');
+        assert_eq!(
+            RNG::from_generator(Constant(u64::MAX)).random::<char>(),
+            char::MAX
+        );
+        let mut rng = RNG::new(11);
+        for _ in 0..1000 {
+            let value = rng.random::<char>() as u32;
+            assert!(!(0xD800..0xE000).contains(&value));
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! A byte stream reader over any generator, see [`RandomBytes`].
+
+use crate::{AnyAlgorithm, RNG, RngAlgorithm};
+use std::io::{self, Read};
+
+/// Reads an endless stream of random bytes from an [`RNG`] (requires `std` feature)
+///
+/// Each output is split into eight little-endian bytes, and the unread part of
+/// a word is kept for the next read. The stream is therefore the same however
+/// the reads are sized, so a seeded generator always writes the same file.
+/// Use [`Read::take`] to stop after a number of bytes.
+///
+/// # Example
+/// ```rust
+/// use simple_rng::{RNG, RandomBytes};
+/// use std::io::Read;
+///
+/// let mut data = Vec::new();
+/// RandomBytes::new(RNG::new(42))
+///     .take(1000)
+///     .read_to_end(&mut data)
+///     .unwrap();
+/// assert_eq!(data.len(), 1000);
+/// ```
+pub struct RandomBytes<A: RngAlgorithm = AnyAlgorithm> {
+    rng: RNG<A>,
+    // Bytes of the last output, consumed from `offset`
+    pending: [u8; 8],
+    offset: usize,
+}
+
+impl<A: RngAlgorithm> RandomBytes<A> {
+    /// Stream bytes from `rng`
+    pub fn new(rng: RNG<A>) -> Self {
+        Self {
+            rng,
+            pending: [0; 8],
+            offset: 8,
+        }
+    }
+
+    /// Take the generator back out, dropping any unread bytes of its last output
+    pub fn into_inner(self) -> RNG<A> {
+        self.rng
+    }
+}
+
+impl<A: RngAlgorithm> Read for RandomBytes<A> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = buf.len();
+        let buffered = (8 - self.offset).min(len);
+        let (head, rest) = buf.split_at_mut(buffered);
+        head.copy_from_slice(&self.pending[self.offset..self.offset + buffered]);
+        self.offset += buffered;
+
+        let (chunks, tail) = rest.as_chunks_mut::<8>();
+        for chunk in chunks {
+            *chunk = self.rng.next().to_le_bytes();
+        }
+        if !tail.is_empty() {
+            self.pending = self.rng.next().to_le_bytes();
+            self.offset = tail.len();
+            tail.copy_from_slice(&self.pending[..self.offset]);
+        }
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Odd read sizes give the same stream as whole words
+    fn test_read_is_contiguous() {
+        let mut expected = [0u8; 40];
+        RNG::new(7).fill_bytes(&mut expected);
+
+        let mut reader = RandomBytes::new(RNG::new(7));
+        let mut out = [0u8; 40];
+        let mut filled = 0;
+        for size in [3, 1, 9, 0, 5, 22] {
+            let read = reader.read(&mut out[filled..filled + size]).unwrap();
+            assert_eq!(read, size);
+            filled += size;
+        }
+        assert_eq!(out, expected);
+    }
+}
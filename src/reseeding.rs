@@ -0,0 +1,223 @@
+//! Periodic reseeding for any seedable generator, see [`Reseeding`].
+
+use crate::{RngAlgorithm, SeedSeq, SeedableAlgorithm};
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+// Counts salts handed out, so copies made in one process never share one
+#[cfg(target_has_atomic = "ptr")]
+static SALTS: AtomicUsize = AtomicUsize::new(0);
+
+/// Wraps a generator and reseeds it from a second generator every `threshold` outputs
+///
+/// Each reseed draws 32 bytes from `reseeder` and passes them to
+/// [`SeedableAlgorithm::from_seed_bytes`]. A `threshold` of 0 turns off
+/// reseeding by count. With the `std` feature, a change of process id is also
+/// detected, so a forked server worker reseeds before its first output instead
+/// of repeating its parent's stream. That check costs one `getpid` call per
+/// output.
+///
+/// The `reseeder` is copied along with everything else when the wrapper is
+/// cloned or its process is forked, so the copy gets a salt of fresh entropy
+/// that is mixed into each of its reseeds from then on. The salt holds OS
+/// entropy where there is some, the process id with `std`, and a per-process
+/// counter. The copy reseeds before its first output, and it and the original
+/// diverge from that reseed on.
+///
+/// # Example
+/// ```rust
+/// use simple_rng::{RNG, Reseeding, SeedableAlgorithm, SplitMix64, Xoshiro256PlusPlus};
+/// let inner = Xoshiro256PlusPlus::from_seed(1);
+/// let reseeder = SplitMix64::from_seed(2);
+/// let mut rng = RNG::from_generator(Reseeding::new(inner, reseeder, 1 << 16));
+/// let value = rng.next();
+/// println!("{}", value);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Reseeding<A: SeedableAlgorithm, R: RngAlgorithm> {
+    generator: A,
+    reseeder: R,
+    threshold: u64,
+    // Outputs left before the next reseed
+    remaining: u64,
+    // Mixed into every reseed of a clone or forked copy
+    salt: Option<SeedSeq>,
+    #[cfg(feature = "std")]
+    pid: u32,
+}
+
+impl<A: SeedableAlgorithm, R: RngAlgorithm> Reseeding<A, R> {
+    /// Wrap `generator`, reseeding it from `reseeder` every `threshold` outputs
+    pub fn new(generator: A, reseeder: R, threshold: u64) -> Self {
+        Self {
+            generator,
+            reseeder,
+            threshold,
+            remaining: Self::budget(threshold),
+            salt: None,
+            #[cfg(feature = "std")]
+            pid: std::process::id(),
+        }
+    }
+
+    fn budget(threshold: u64) -> u64 {
+        if threshold == 0 { u64::MAX } else { threshold }
+    }
+
+    /// Reseed now, restarting the count towards the next reseed
+    pub fn reseed(&mut self) {
+        let mut words = [0u64; 4];
+        self.reseeder.fill_u64(&mut words);
+        let mut seed = [0u8; 32];
+        for (chunk, word) in seed.chunks_exact_mut(8).zip(words) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        if let Some(salt) = &self.salt {
+            seed = salt.clone().add_bytes(&seed).seed_bytes();
+        }
+        self.generator = A::from_seed_bytes(seed);
+        self.remaining = Self::budget(self.threshold);
+    }
+
+    // Reseed if the budget is used up or the process was forked
+    fn check(&mut self) {
+        #[cfg(feature = "std")]
+        {
+            let pid = std::process::id();
+            if pid != self.pid {
+                self.pid = pid;
+                self.salt = Some(fresh_salt());
+                self.remaining = 0;
+            }
+        }
+        if self.remaining == 0 {
+            self.reseed();
+        }
+    }
+}
+
+impl<A: SeedableAlgorithm, R: RngAlgorithm> RngAlgorithm for Reseeding<A, R> {
+    fn next_u64(&mut self) -> u64 {
+        self.check();
+        self.remaining -= 1;
+        self.generator.next_u64()
+    }
+
+    // Fill in runs that end at each reseed, so block generators keep writing whole blocks
+    fn fill_u64(&mut self, mut dest: &mut [u64]) {
+        while !dest.is_empty() {
+            self.check();
+            let len = dest
+                .len()
+                .min(self.remaining.try_into().unwrap_or(usize::MAX));
+            let (head, rest) = dest.split_at_mut(len);
+            self.generator.fill_u64(head);
+            self.remaining -= len as u64;
+            dest = rest;
+        }
+    }
+}
+
+impl<A: SeedableAlgorithm + Clone, R: RngAlgorithm + Clone> Clone for Reseeding<A, R> {
+    fn clone(&self) -> Self {
+        Self {
+            generator: self.generator.clone(),
+            reseeder: self.reseeder.clone(),
+            threshold: self.threshold,
+            remaining: 0,
+            salt: Some(fresh_salt()),
+            #[cfg(feature = "std")]
+            pid: self.pid,
+        }
+    }
+}
+
+// A salt no other copy of the wrapper has, from whatever entropy the target offers
+fn fresh_salt() -> SeedSeq {
+    #[allow(unused_mut)]
+    let mut salt = SeedSeq::new();
+    #[cfg(target_has_atomic = "ptr")]
+    {
+        salt = salt.add_u64(SALTS.fetch_add(1, Ordering::Relaxed) as u64);
+    }
+    #[cfg(feature = "std")]
+    {
+        salt = salt.add_u64(std::process::id() as u64);
+    }
+    #[cfg(any(feature = "std", feature = "getrandom"))]
+    {
+        let mut entropy = [0u8; 32];
+        if crate::entropy::fill_os_entropy(&mut entropy) {
+            salt = salt.add_bytes(&entropy);
+        }
+    }
+    salt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RNG, SplitMix64, Xoshiro256PlusPlus};
+
+    #[test]
+    /// The generator restarts from the reseeder's bytes after `threshold` outputs
+    fn test_reseeds_after_threshold() {
+        let mut rng = RNG::from_generator(Reseeding::new(
+            Xoshiro256PlusPlus::new(1),
+            SplitMix64::new(2),
+            3,
+        ));
+        let mut plain = Xoshiro256PlusPlus::new(1);
+        for _ in 0..3 {
+            assert_eq!(rng.next(), plain.next_u64());
+        }
+
+        let mut reseeder = SplitMix64::new(2);
+        let mut seed = [0u8; 32];
+        for chunk in seed.chunks_exact_mut(8) {
+            chunk.copy_from_slice(&reseeder.next_u64().to_le_bytes());
+        }
+        let mut reseeded = Xoshiro256PlusPlus::from_seed_bytes(seed);
+        let mut out = [0u64; 3];
+        rng.state.fill_u64(&mut out);
+        for value in out {
+            assert_eq!(value, reseeded.next_u64());
+        }
+    }
+
+    #[test]
+    /// A threshold of 0 never reseeds, and a clone reseeds straight away
+    fn test_disabled_and_clone() {
+        let mut wrapped = Reseeding::new(Xoshiro256PlusPlus::new(4), SplitMix64::new(5), 0);
+        let mut plain = Xoshiro256PlusPlus::new(4);
+        let mut out = [0u64; 1000];
+        wrapped.fill_u64(&mut out);
+        for value in out {
+            assert_eq!(value, plain.next_u64());
+        }
+        let mut copy = wrapped.clone();
+        assert_ne!(copy.next_u64(), wrapped.next_u64());
+    }
+
+    #[test]
+    /// A clone and the original keep diverging across later reseeds, though
+    /// they share the reseeder's sequence
+    fn test_clone_diverges_after_reseed() {
+        let mut original = Reseeding::new(Xoshiro256PlusPlus::new(6), SplitMix64::new(7), 4);
+        let mut copy = original.clone();
+        let mut copy_of_copy = copy.clone();
+        for _ in 0..4 {
+            original.next_u64();
+        }
+        let mut first = [0u64; 12];
+        let mut second = [0u64; 12];
+        let mut third = [0u64; 12];
+        original.fill_u64(&mut first);
+        copy.fill_u64(&mut second);
+        copy_of_copy.fill_u64(&mut third);
+        for ((a, b), c) in first.chunks(4).zip(second.chunks(4)).zip(third.chunks(4)) {
+            assert_ne!(a, b);
+            assert_ne!(b, c);
+        }
+    }
+}
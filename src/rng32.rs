@@ -0,0 +1,99 @@
+//! Compact generator for 32-bit microcontrollers, see [`RNG32`].
+
+use crate::Xoshiro128PlusPlus;
+use core::ops::RangeBounds;
+
+/// A compact random number generator with 32-bit outputs
+///
+/// Backed by xoshiro128++, which steps with 32-bit adds, xors and rotations only,
+/// so nothing is emulated on cores without a 64-bit multiplier such as the
+/// Cortex-M0. The whole generator is 16 bytes, against the full [`crate::RNG`],
+/// which is as large as its largest algorithm.
+///
+/// # Example
+/// ```rust
+/// use simple_rng::RNG32;
+/// let mut rng = RNG32::new(42);
+/// let value = rng.next_u32();
+/// let roll = rng.gen_range(1..=6);
+/// println!("{} {}", value, roll);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RNG32 {
+    state: Xoshiro128PlusPlus,
+}
+
+impl RNG32 {
+    /// Create a new RNG32 with the given seed
+    ///
+    /// The seed is expanded to the full state once, here, so the 64-bit
+    /// arithmetic of the expansion stays out of the generation loop.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: Xoshiro128PlusPlus::new(seed),
+        }
+    }
+
+    /// Advance the RNG and return the next random u32 value
+    pub fn next_u32(&mut self) -> u32 {
+        self.state.next_u32()
+    }
+
+    /// Generate a random integer in a range, such as `1..=6` or `0..n`
+    ///
+    /// Uses Lemire's multiply-shift rejection method, which is exactly uniform
+    /// and needs no division in the common case.
+    pub fn gen_range<R: RangeBounds<u32>>(&mut self, range: R) -> u32 {
+        let Some((low, high)) = inclusive_bounds!(range, u32) else {
+            panic!("cannot sample empty range")
+        };
+        let range = high - low;
+        if range == u32::MAX {
+            return self.next_u32();
+        }
+        let bound = range + 1;
+        let mut product = self.next_u32() as u64 * bound as u64;
+        if (product as u32) < bound {
+            let threshold = bound.wrapping_neg() % bound;
+            while (product as u32) < threshold {
+                product = self.next_u32() as u64 * bound as u64;
+            }
+        }
+        (product >> 32) as u32 + low
+    }
+
+    /// Generate a random floating-point value in [0.0, 1.0)
+    pub fn gen_float(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Generate a random boolean value
+    pub fn gen_bool(&mut self) -> bool {
+        self.next_u32() >> 31 == 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// RNG32 produces the xoshiro128++ stream
+    fn test_matches_backend() {
+        let mut rng = RNG32::new(9);
+        let mut backend = Xoshiro128PlusPlus::new(9);
+        for _ in 0..10 {
+            assert_eq!(rng.next_u32(), backend.next_u32());
+        }
+    }
+
+    #[test]
+    /// gen_range covers the full u32 range without overflowing
+    fn test_gen_range_full() {
+        let mut rng = RNG32::new(1);
+        rng.gen_range(..);
+        for _ in 0..100 {
+            assert!((3..=5).contains(&rng.gen_range(3..=5)));
+        }
+    }
+}
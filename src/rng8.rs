@@ -0,0 +1,122 @@
+//! Tiny generator for 8-bit microcontrollers, see [`RNG8`].
+
+use core::ops::RangeBounds;
+
+/// A tiny random number generator with 8- and 16-bit outputs
+///
+/// Marsaglia's xorshift on four bytes of state, with the shift triple (1, 1, 3)
+/// chosen for a full period of 2^32 - 1. Each step is a handful of single-bit
+/// shifts and xors on bytes, so on AVR it costs a few dozen cycles with no
+/// multi-byte arithmetic at all. Quality is modest; it suits games, jitter and
+/// blinking lights rather than simulations.
+///
+/// # Example
+/// ```rust
+/// use simple_rng::RNG8;
+/// let mut rng = RNG8::new(42);
+/// let byte = rng.next_u8();
+/// let word = rng.next_u16();
+/// println!("{} {}", byte, word);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RNG8 {
+    s: [u8; 4],
+}
+
+impl RNG8 {
+    /// Create a new RNG8 with the given seed
+    ///
+    /// The all-zero state never changes, so a seed of 0 starts from state 1
+    /// instead. The first 16 outputs are skipped so nearby seeds drift apart.
+    pub fn new(seed: u32) -> Self {
+        let seed = if seed == 0 { 1 } else { seed };
+        let mut rng = Self {
+            s: seed.to_le_bytes(),
+        };
+        for _ in 0..16 {
+            rng.next_u8();
+        }
+        rng
+    }
+
+    /// Advance the RNG and return the next random u8 value
+    pub fn next_u8(&mut self) -> u8 {
+        let [x, y, z, w] = self.s;
+        let t = x ^ (x << 1);
+        let next = w ^ (w >> 3) ^ t ^ (t >> 1);
+        self.s = [y, z, w, next];
+        next
+    }
+
+    /// Return the next random u16 value from two outputs, the first in the low byte
+    pub fn next_u16(&mut self) -> u16 {
+        let low = self.next_u8() as u16;
+        let high = self.next_u8() as u16;
+        (high << 8) | low
+    }
+
+    /// Generate a random integer in a range, such as `1..=6` or `0..n`
+    ///
+    /// Uses Lemire's multiply-shift rejection method, which is exactly uniform
+    /// and needs only an 8x8-bit multiply in the common case.
+    pub fn gen_range<R: RangeBounds<u8>>(&mut self, range: R) -> u8 {
+        let Some((low, high)) = inclusive_bounds!(range, u8) else {
+            panic!("cannot sample empty range")
+        };
+        let range = high - low;
+        if range == u8::MAX {
+            return self.next_u8();
+        }
+        let bound = range + 1;
+        let mut product = self.next_u8() as u16 * bound as u16;
+        if (product as u8) < bound {
+            let threshold = bound.wrapping_neg() % bound;
+            while (product as u8) < threshold {
+                product = self.next_u8() as u16 * bound as u16;
+            }
+        }
+        (product >> 8) as u8 + low
+    }
+
+    /// Generate a random boolean value
+    pub fn gen_bool(&mut self) -> bool {
+        self.next_u8() >> 7 == 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Output matches the byte xorshift for the state {1, 2, 3, 4}
+    fn test_reference_output() {
+        let mut rng = RNG8 { s: [1, 2, 3, 4] };
+        for value in [6, 3, 4, 14, 0, 7, 13, 23] {
+            assert_eq!(rng.next_u8(), value);
+        }
+    }
+
+    #[test]
+    /// A zero seed does not leave the generator stuck at zero
+    fn test_zero_seed() {
+        let mut rng = RNG8::new(0);
+        assert!((0..8).any(|_| rng.next_u8() != 0));
+    }
+
+    #[test]
+    /// Every value of a range that doesn't divide 256 is hit equally often
+    fn test_gen_range_uniform() {
+        let mut rng = RNG8::new(5);
+        let mut counts = [0u32; 3];
+        // The full period holds each byte sequence equally often, so over
+        // 3 * 2^16 draws the small counts should stay close
+        for _ in 0..3 * (1 << 16) {
+            counts[rng.gen_range(0..3) as usize] += 1;
+        }
+        let mean = (1 << 16) as f64;
+        for count in counts {
+            assert!((count as f64 - mean).abs() < mean * 0.02);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Mixing several seed inputs into generator state, see [`SeedSeq`].
+
+use crate::SplitMix64;
+
+// One SplitMix64 step, used as a bit mixer that maps 0 to a non-zero word
+fn mix(value: u64) -> u64 {
+    SplitMix64::new(value).next_u64()
+}
+
+/// Mixes any number of integer and byte inputs into seed material
+///
+/// Every input influences every output word, so seeds that differ in a
+/// single bit, such as consecutive worker ids, still give unrelated states.
+/// Outputs are hashes of the mixed inputs, so an all-zero state, which traps
+/// generators like xoshiro, is never produced in practice even when every
+/// input is zero. The order of the inputs matters.
+///
+/// # Example
+/// ```rust
+/// use simple_rng::{RNG, SeedSeq, Xoshiro256PlusPlus};
+/// let seq = SeedSeq::new().add_u64(42).add_u64(7).add_bytes(b"run-3");
+/// let mut rng = RNG::<Xoshiro256PlusPlus>::from_seed_seq(&seq);
+/// let mut state = [0u64; 16];
+/// seq.fill(&mut state);
+/// println!("{} {:?}", rng.next(), state);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeedSeq {
+    pool: [u64; 4],
+    absorbed: u64,
+}
+
+impl SeedSeq {
+    /// Start with no inputs
+    pub fn new() -> Self {
+        Self {
+            pool: [0; 4],
+            absorbed: 0,
+        }
+    }
+
+    /// Mix in a 64-bit value
+    pub fn add_u64(mut self, value: u64) -> Self {
+        self.absorb(value);
+        self
+    }
+
+    /// Mix in a byte string, which may be any length
+    pub fn add_bytes(mut self, bytes: &[u8]) -> Self {
+        let (words, tail) = bytes.as_chunks::<8>();
+        for word in words {
+            self.absorb(u64::from_le_bytes(*word));
+        }
+        if !tail.is_empty() {
+            let mut word = [0u8; 8];
+            word[..tail.len()].copy_from_slice(tail);
+            self.absorb(u64::from_le_bytes(word));
+        }
+        // The length tells trailing zero bytes apart from padding
+        self.absorb(bytes.len() as u64);
+        self
+    }
+
+    // Each input is mixed with its position into one of the four pool words,
+    // which are mixed with it in turn
+    fn absorb(&mut self, value: u64) {
+        let slot = &mut self.pool[(self.absorbed % 4) as usize];
+        *slot = mix(*slot ^ mix(value ^ self.absorbed));
+        self.absorbed += 1;
+    }
+
+    /// Expand the inputs into as many words as `dest` holds
+    ///
+    /// Word `k` hashes `k` with the whole pool, so filling a shorter slice
+    /// gives a prefix of a longer fill.
+    pub fn fill(&self, dest: &mut [u64]) {
+        for (k, word) in dest.iter_mut().enumerate() {
+            *word = self.pool.iter().fold(k as u64, |acc, &p| mix(acc ^ p));
+        }
+    }
+
+    /// Expand the inputs into a single 64-bit seed, for [`crate::RNG::with_algorithm`]
+    pub fn seed_u64(&self) -> u64 {
+        let mut word = [0u64];
+        self.fill(&mut word);
+        word[0]
+    }
+
+    /// Expand the inputs into 32 seed bytes, for [`crate::RNG::from_seed_bytes`]
+    pub fn seed_bytes(&self) -> [u8; 32] {
+        let mut words = [0u64; 4];
+        self.fill(&mut words);
+        let mut bytes = [0u8; 32];
+        for (chunk, word) in bytes.chunks_exact_mut(8).zip(words) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+}
+
+impl Default for SeedSeq {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Zero inputs still give non-zero material, and order and length matter
+    fn test_mixing() {
+        let zeros = SeedSeq::new().add_u64(0).add_u64(0);
+        let mut words = [0u64; 8];
+        zeros.fill(&mut words);
+        assert!(words.iter().all(|&word| word != 0));
+
+        let forward = SeedSeq::new().add_u64(1).add_u64(2).seed_u64();
+        let backward = SeedSeq::new().add_u64(2).add_u64(1).seed_u64();
+        assert_ne!(forward, backward);
+
+        let short = SeedSeq::new().add_bytes(b"ab").seed_u64();
+        let padded = SeedSeq::new().add_bytes(b"ab\0").seed_u64();
+        assert_ne!(short, padded);
+    }
+
+    #[test]
+    /// Shorter fills are prefixes of longer ones
+    fn test_fill_prefix() {
+        let seq = SeedSeq::new().add_u64(9);
+        let mut long = [0u64; 6];
+        let mut short = [0u64; 2];
+        seq.fill(&mut long);
+        seq.fill(&mut short);
+        assert_eq!(short, long[..2]);
+        assert_eq!(seq.seed_u64(), long[0]);
+    }
+}
@@ -0,0 +1,279 @@
+//! Checkpointing an RNG mid-run, see [`Snapshot`] and [`RNG::to_bytes`].
+
+use crate::algorithms::State;
+use crate::algorithms::portable::Portable;
+use crate::{AnyAlgorithm, RNG, RngAlgorithm, RngError};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Leading bytes of every encoded state
+const MAGIC: &[u8; 4] = b"SRNG";
+/// Version of the encoding written by [`RNG::to_bytes`]
+const FORMAT_VERSION: u8 = 1;
+
+/// A saved copy of an [`RNG`]'s full state, taken by [`RNG::snapshot`]
+///
+/// Restoring it with [`RNG::restore`] makes the generator repeat exactly the
+/// outputs it produced after the snapshot, including a half-used
+/// [`RNG::next_u32`] output.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snapshot<A: RngAlgorithm> {
+    state: A,
+    cached_u32: Option<u32>,
+}
+
+impl<A: RngAlgorithm + Clone> RNG<A> {
+    /// Save the current state, to resume from later with [`RNG::restore`]
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::RNG;
+    /// let mut rng = RNG::new(42);
+    /// let checkpoint = rng.snapshot();
+    /// let first = rng.next();
+    /// rng.restore(&checkpoint);
+    /// assert_eq!(rng.next(), first);
+    /// ```
+    pub fn snapshot(&self) -> Snapshot<A> {
+        Snapshot {
+            state: self.state.clone(),
+            cached_u32: self.cached_u32,
+        }
+    }
+
+    /// Return to the state saved in `snapshot`
+    pub fn restore(&mut self, snapshot: &Snapshot<A>) {
+        self.state = snapshot.state.clone();
+        self.cached_u32 = snapshot.cached_u32;
+    }
+}
+
+impl RNG {
+    /// Encode the full state, including the algorithm, as portable bytes
+    ///
+    /// The encoding is versioned and the same on every platform, so saved games
+    /// and simulation checkpoints can be restored with [`RNG::from_bytes`] by
+    /// later crate versions and on other machines. It holds the algorithm's
+    /// internal state, which for the cryptographic algorithms includes the key.
+    /// Only available with the `alloc` feature.
+    ///
+    /// # Example
+    /// ```rust
+    /// use simple_rng::{Algorithm, RNG};
+    /// let mut rng = RNG::with_algorithm(42, Algorithm::Sfc64);
+    /// let saved = rng.to_bytes();
+    /// let first = rng.next();
+    /// let mut loaded = RNG::from_bytes(&saved).unwrap();
+    /// assert_eq!(loaded.next(), first);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut out = |piece: &[u8]| bytes.extend_from_slice(piece);
+        out(MAGIC);
+        out(&[FORMAT_VERSION]);
+        match self.cached_u32 {
+            Some(half) => {
+                out(&[1]);
+                out(&half.to_le_bytes());
+            }
+            None => out(&[0]),
+        }
+        self.state.0.encode(&mut out);
+        bytes
+    }
+
+    /// Restore an RNG from bytes written by [`RNG::to_bytes`]
+    ///
+    /// Returns [`RngError::InvalidState`] if the bytes are truncated, corrupt,
+    /// from a newer format version, or for an algorithm whose feature is not
+    /// enabled in this build. States no seed can produce are rejected too,
+    /// such as an even LCG or PCG increment or an all-zero xoshiro state,
+    /// since the generator would be stuck or have a tiny period.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RngError> {
+        Self::decode(bytes).ok_or(RngError::InvalidState)
+    }
+
+    fn decode(mut input: &[u8]) -> Option<Self> {
+        let input = &mut input;
+        if <[u8; 4]>::decode(input)? != *MAGIC || u8::decode(input)? != FORMAT_VERSION {
+            return None;
+        }
+        let cached_u32 = match u8::decode(input)? {
+            0 => None,
+            1 => Some(u32::decode(input)?),
+            _ => return None,
+        };
+        let state = State::decode(input)?;
+        if !input.is_empty() {
+            return None;
+        }
+        let mut rng = Self::from_generator(AnyAlgorithm(state));
+        rng.cached_u32 = cached_u32;
+        Some(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Algorithm, RNG};
+
+    #[test]
+    /// Restoring repeats the outputs after the snapshot, including a cached half
+    fn test_restore_repeats() {
+        let mut rng = RNG::with_algorithm(24, Algorithm::Threefry2x64);
+        rng.next_u32();
+        let checkpoint = rng.snapshot();
+        let after: [u32; 5] = core::array::from_fn(|_| rng.next_u32());
+        rng.restore(&checkpoint);
+        let again: [u32; 5] = core::array::from_fn(|_| rng.next_u32());
+        assert_eq!(after, again);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    /// Every algorithm round-trips through bytes mid-block and mid-output
+    fn test_bytes_round_trip() {
+        let algorithms = [
+            Algorithm::Lcg,
+            #[cfg(feature = "pcg")]
+            Algorithm::Pcg,
+            Algorithm::Xoshiro256PlusPlus,
+            Algorithm::Xoroshiro128Plus,
+            Algorithm::SplitMix64,
+            Algorithm::Xorshift64Star,
+            #[cfg(feature = "pcg")]
+            Algorithm::Pcg64,
+            #[cfg(feature = "mt19937")]
+            Algorithm::Mt19937_64,
+            Algorithm::WyRand,
+            Algorithm::Sfc64,
+            Algorithm::Jsf64,
+            #[cfg(feature = "romu")]
+            Algorithm::RomuDuoJr,
+            #[cfg(feature = "romu")]
+            Algorithm::RomuTrio,
+            Algorithm::Mcg128,
+            Algorithm::Philox4x32,
+            Algorithm::Threefry2x64,
+            Algorithm::Mwc256,
+            Algorithm::Msws,
+            #[cfg(feature = "crypto")]
+            Algorithm::ChaCha20,
+            #[cfg(feature = "crypto")]
+            Algorithm::ChaCha12,
+            #[cfg(feature = "crypto")]
+            Algorithm::ChaCha8,
+            #[cfg(feature = "drbg")]
+            Algorithm::CtrDrbg,
+            #[cfg(feature = "drbg")]
+            Algorithm::HmacDrbg,
+            #[cfg(feature = "drbg")]
+            Algorithm::HashDrbg,
+            #[cfg(feature = "isaac")]
+            Algorithm::Isaac64,
+            Algorithm::Xoshiro128PlusPlus,
+        ];
+        for algorithm in algorithms {
+            let mut rng = RNG::with_algorithm(31, algorithm);
+            rng.next();
+            rng.next_u32();
+            let saved = rng.to_bytes();
+            let mut loaded = RNG::from_bytes(&saved).unwrap();
+            let expected: [u32; 9] = core::array::from_fn(|_| rng.next_u32());
+            let actual: [u32; 9] = core::array::from_fn(|_| loaded.next_u32());
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    /// The encoding is fixed, so states saved by older builds keep loading
+    fn test_bytes_layout() {
+        let mut rng = RNG::new(5);
+        let mut expected = b"SRNG\x01\x00\x00".to_vec();
+        expected.extend_from_slice(&rng.state.0.seed_word().to_le_bytes());
+        assert_eq!(rng.to_bytes()[..15], expected[..]);
+        assert_eq!(rng.to_bytes().len(), 23);
+        rng.next_u32();
+        assert_eq!(rng.to_bytes()[5], 1);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    /// Truncated, padded or corrupt bytes are rejected
+    fn test_bytes_invalid() {
+        let saved = RNG::with_algorithm(2, Algorithm::Philox4x32).to_bytes();
+        for len in 0..saved.len() {
+            assert_eq!(
+                RNG::from_bytes(&saved[..len]).err(),
+                Some(crate::RngError::InvalidState)
+            );
+        }
+        let mut padded = saved.clone();
+        padded.push(0);
+        assert!(RNG::from_bytes(&padded).is_err());
+        let mut future = saved.clone();
+        future[4] = 2;
+        assert!(RNG::from_bytes(&future).is_err());
+        let mut bad_index = saved.clone();
+        let last = bad_index.len() - 8;
+        bad_index[last] = 3;
+        assert!(RNG::from_bytes(&bad_index).is_err());
+        let mut bad_tag = saved;
+        bad_tag[6] = 200;
+        assert!(RNG::from_bytes(&bad_tag).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    /// States no seed can reach, which would get stuck, are rejected
+    fn test_bytes_degenerate() {
+        let invalid = Some(crate::RngError::InvalidState);
+        // The state starts after the header, cache flag and tag
+        let check_zeroed = |algorithm: Algorithm| {
+            let mut zeroed = RNG::with_algorithm(8, algorithm).to_bytes();
+            zeroed[7..].fill(0);
+            assert_eq!(RNG::from_bytes(&zeroed).err(), invalid, "{algorithm:?}");
+        };
+        check_zeroed(Algorithm::Xoshiro256PlusPlus);
+        check_zeroed(Algorithm::Xoroshiro128Plus);
+        check_zeroed(Algorithm::Xorshift64Star);
+        check_zeroed(Algorithm::Xoshiro128PlusPlus);
+        check_zeroed(Algorithm::Jsf64);
+        check_zeroed(Algorithm::Mwc256);
+        #[cfg(feature = "romu")]
+        check_zeroed(Algorithm::RomuDuoJr);
+        #[cfg(feature = "romu")]
+        check_zeroed(Algorithm::RomuTrio);
+        #[cfg(feature = "mt19937")]
+        check_zeroed(Algorithm::Mt19937_64);
+
+        // Clear the low bit of the increment, or the Msws Weyl step, the last
+        // field
+        let check_even = |algorithm: Algorithm, width: usize| {
+            let mut even = RNG::with_algorithm(8, algorithm).to_bytes();
+            let low = even.len() - width;
+            even[low] &= !1;
+            assert_eq!(RNG::from_bytes(&even).err(), invalid, "{algorithm:?}");
+        };
+        check_even(Algorithm::Lcg, 8);
+        check_even(Algorithm::Msws, 8);
+        #[cfg(feature = "pcg")]
+        check_even(Algorithm::Pcg, 8);
+        #[cfg(feature = "pcg")]
+        check_even(Algorithm::Pcg64, 16);
+
+        let mut even = RNG::with_algorithm(8, Algorithm::Mcg128).to_bytes();
+        even[7] &= !1;
+        assert_eq!(RNG::from_bytes(&even).err(), invalid);
+
+        // Mwc256 keeps its carry below MULTIPLIER - 1, but a zero carry is fine
+        let mut carry = RNG::with_algorithm(8, Algorithm::Mwc256).to_bytes();
+        let last = carry.len() - 8;
+        carry[last..].copy_from_slice(&0xfff62cf2ccc0cdaeu64.to_le_bytes());
+        assert_eq!(RNG::from_bytes(&carry).err(), invalid);
+        carry[last..].fill(0);
+        assert!(RNG::from_bytes(&carry).is_ok());
+    }
+}
@@ -0,0 +1,139 @@
+//! A generator that can be shared through an `Arc`, see [`SyncRng`].
+
+use crate::{AnyAlgorithm, RNG, Random, RngAlgorithm, SampleUniform};
+use core::ops::RangeBounds;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// An [`RNG`] behind a mutex, so it is `Send + Sync` and can live in an `Arc`
+/// (requires `std` feature)
+///
+/// The common methods lock for a single call. [`SyncRng::with`] holds the lock
+/// across several calls, and `&SyncRng` implements [`RngAlgorithm`], so
+/// [`RNG::from_generator`] gives a handle with every `RNG` helper. For a
+/// generator that many threads hit at once, [`crate::AtomicRng`] never blocks.
+///
+/// # Example
+/// ```rust
+/// use simple_rng::{RNG, SyncRng};
+/// use std::sync::Arc;
+///
+/// let shared = Arc::new(SyncRng::new(RNG::new(42)));
+/// let worker = Arc::clone(&shared);
+/// let value = std::thread::spawn(move || worker.gen_range(1..=100))
+///     .join()
+///     .unwrap();
+/// println!("{} {}", value, shared.next());
+/// ```
+pub struct SyncRng<A: RngAlgorithm = AnyAlgorithm> {
+    rng: Mutex<RNG<A>>,
+}
+
+impl<A: RngAlgorithm> SyncRng<A> {
+    /// Share `rng`
+    pub fn new(rng: RNG<A>) -> Self {
+        Self {
+            rng: Mutex::new(rng),
+        }
+    }
+
+    /// Take the generator back out
+    pub fn into_inner(self) -> RNG<A> {
+        self.rng
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // A panic mid-call cannot leave the state unusable, so poisoning is ignored
+    fn lock(&self) -> MutexGuard<'_, RNG<A>> {
+        self.rng.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Run `f` with the generator locked, for several draws in a row
+    pub fn with<R>(&self, f: impl FnOnce(&mut RNG<A>) -> R) -> R {
+        f(&mut self.lock())
+    }
+
+    /// Next random u64, see [`RNG::next`]
+    pub fn next(&self) -> u64 {
+        self.lock().next()
+    }
+
+    /// Random value in `range`, see [`RNG::gen_range`]
+    pub fn gen_range<T: SampleUniform, R: RangeBounds<T>>(&self, range: R) -> T {
+        self.lock().gen_range(range)
+    }
+
+    /// Random float in [0.0, 1.0), see [`RNG::gen_float`]
+    pub fn gen_float(&self) -> f64 {
+        self.lock().gen_float()
+    }
+
+    /// Random boolean, see [`RNG::gen_bool`]
+    pub fn gen_bool(&self) -> bool {
+        self.lock().gen_bool()
+    }
+
+    /// Random value of any [`Random`] type, see [`RNG::random`]
+    pub fn random<T: Random>(&self) -> T {
+        self.lock().random()
+    }
+
+    /// Fill a byte buffer, see [`RNG::fill_bytes`]
+    pub fn fill_bytes(&self, dest: &mut [u8]) {
+        self.lock().fill_bytes(dest)
+    }
+}
+
+impl<A: RngAlgorithm> RngAlgorithm for &SyncRng<A> {
+    fn next_u64(&mut self) -> u64 {
+        SyncRng::next(self)
+    }
+
+    fn fill_u64(&mut self, dest: &mut [u64]) {
+        let mut rng = self.lock();
+        for word in dest {
+            *word = rng.next();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    /// Calls through the lock continue the wrapped sequence
+    fn test_same_sequence() {
+        let shared = SyncRng::new(RNG::new(6));
+        let mut plain = RNG::new(6);
+        assert_eq!(shared.next(), plain.next());
+        assert_eq!(
+            shared.gen_range(0..=u64::MAX),
+            plain.gen_range(0..=u64::MAX)
+        );
+        assert_eq!(RNG::from_generator(&shared).next(), plain.next());
+        assert_eq!(shared.with(|rng| rng.next()), plain.next());
+        assert_eq!(shared.into_inner().next(), plain.next());
+    }
+
+    #[test]
+    /// Threads can share one generator through an Arc
+    fn test_shared_across_threads() {
+        let shared = Arc::new(SyncRng::new(RNG::new(7)));
+        let workers: std::vec::Vec<_> = (0..4)
+            .map(|_| {
+                let shared = Arc::clone(&shared);
+                std::thread::spawn(move || (0..100).map(|_| shared.next()).max())
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+        let mut plain = RNG::new(7);
+        for _ in 0..400 {
+            plain.next();
+        }
+        assert_eq!(shared.next(), plain.next());
+    }
+}
@@ -0,0 +1,106 @@
+//! A lazily seeded generator per thread, see [`thread_rng`].
+
+use crate::entropy::fill_os_entropy;
+use crate::{RNG, Random, RngAlgorithm, SampleUniform, SeedSeq, Xoshiro256PlusPlus};
+use core::cell::RefCell;
+use core::ops::RangeBounds;
+use std::rc::Rc;
+
+std::thread_local! {
+    static THREAD_RNG: Rc<RefCell<Xoshiro256PlusPlus>> = Rc::new(RefCell::new(seed()));
+}
+
+// OS entropy when there is some, otherwise the clock
+fn seed() -> Xoshiro256PlusPlus {
+    let mut bytes = [0u8; 32];
+    if !fill_os_entropy(&mut bytes) {
+        bytes = SeedSeq::new().add_u64(RNG::from_time().next()).seed_bytes();
+    }
+    Xoshiro256PlusPlus::from_seed_bytes(bytes)
+}
+
+/// Handle to the calling thread's generator, created by [`thread_rng`]
+///
+/// Every handle on a thread shares one xoshiro256++ state, seeded from OS
+/// entropy the first time the thread uses it. Handles cannot be sent to other
+/// threads, which have generators of their own.
+#[derive(Clone, Debug)]
+pub struct ThreadRng(Rc<RefCell<Xoshiro256PlusPlus>>);
+
+impl RngAlgorithm for ThreadRng {
+    fn next_u64(&mut self) -> u64 {
+        self.0.borrow_mut().next_u64()
+    }
+
+    fn fill_u64(&mut self, dest: &mut [u64]) {
+        self.0.borrow_mut().fill_u64(dest)
+    }
+}
+
+/// The calling thread's generator, for scripts that do not want to pass an
+/// [`RNG`] around (requires `std` feature)
+///
+/// Not reproducible: use [`RNG::new`] or [`RNG::from_seed`] when a run must
+/// be repeatable.
+///
+/// # Example
+/// ```rust
+/// let roll = simple_rng::thread_rng().gen_range(1..=6);
+/// println!("{}", roll);
+/// ```
+pub fn thread_rng() -> RNG<ThreadRng> {
+    RNG::from_generator(ThreadRng(THREAD_RNG.with(Rc::clone)))
+}
+
+/// A random value of any [`Random`] type from [`thread_rng`]
+///
+/// # Example
+/// ```rust
+/// let coin: bool = simple_rng::random();
+/// let byte = simple_rng::random::<u8>();
+/// println!("{} {}", coin, byte);
+/// ```
+pub fn random<T: Random>() -> T {
+    thread_rng().random()
+}
+
+/// A random value in `range` from [`thread_rng`], see [`RNG::gen_range`]
+///
+/// # Example
+/// ```rust
+/// let jitter: i32 = simple_rng::random_range(-50..=50);
+/// println!("{}", jitter);
+/// ```
+pub fn random_range<T: SampleUniform, R: RangeBounds<T>>(range: R) -> T {
+    thread_rng().gen_range(range)
+}
+
+/// Shuffle `slice` in place with [`thread_rng`], see [`RNG::shuffle`]
+pub fn shuffle<T>(slice: &mut [T]) {
+    thread_rng().shuffle(slice)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Handles on one thread share a state, and threads get their own
+    fn test_thread_rng() {
+        let mut first = thread_rng();
+        let mut second = thread_rng();
+        let value = first.next();
+        assert_ne!(second.next(), value);
+
+        let other = std::thread::spawn(|| thread_rng().next()).join().unwrap();
+        assert_ne!(other, value);
+
+        let roll = random_range(1..=6);
+        assert!((1..=6).contains(&roll));
+        let mut deck = [1, 2, 3, 4, 5, 6, 7, 8];
+        shuffle(&mut deck);
+        deck.sort();
+        assert_eq!(deck, [1, 2, 3, 4, 5, 6, 7, 8]);
+        let _: (u8, f64) = random();
+    }
+}
@@ -0,0 +1,429 @@
+//! Uniform sampling of integer, char, duration and float ranges, see
+//! [`SampleUniform`], [`SampleFloat`] and [`Uniform`].
+
+use crate::{RNG, RngAlgorithm, RngError};
+use core::ops::RangeBounds;
+use core::time::Duration;
+
+/// An integer type that [`RNG::gen_range`] and [`Uniform`] can sample
+///
+/// Implemented for every primitive integer, from `u8` to `u128`, `i8` to
+/// `i128`, `usize` and `isize`, so ranges can be given in the type they are
+/// used in without casting. It is also implemented for `char`, skipping the
+/// surrogate code points, which are not valid `char`s, and for `Duration`, to
+/// the nanosecond.
+///
+/// Generic numeric code can use it as a bound in place of a `num-traits`
+/// `PrimInt`, and pick the concrete type at the call site:
+///
+/// ```rust
+/// use simple_rng::{RNG, SampleUniform};
+///
+/// fn jitter<T: SampleUniform>(rng: &mut RNG, low: T, high: T) -> T {
+///     rng.gen_range(low..=high)
+/// }
+///
+/// let mut rng = RNG::new(42);
+/// let small: u8 = jitter(&mut rng, 1, 10);
+/// let wide: i128 = jitter(&mut rng, -1, 1);
+/// println!("{} {}", small, wide);
+/// ```
+pub trait SampleUniform: Copy + PartialOrd {
+    /// Resolve `range` to inclusive `(low, high)` bounds, or `None` if it is empty
+    fn inclusive_bounds<R: RangeBounds<Self>>(range: R) -> Option<(Self, Self)>;
+
+    /// Number of values above `low` up to `high`, where `low <= high`
+    fn span(low: Self, high: Self) -> u128;
+
+    /// The value `offset` places above `low`, where `offset` is within the span
+    fn offset(low: Self, offset: u128) -> Self;
+}
+
+// Signed types measure the span in their unsigned twin, so `MIN..=MAX`
+// doesn't overflow, and add the offset back with wrapping arithmetic
+macro_rules! impl_sample_uniform {
+    ($($ty:ty => $unsigned:ty),* $(,)?) => {$(
+        impl SampleUniform for $ty {
+            fn inclusive_bounds<R: RangeBounds<Self>>(range: R) -> Option<(Self, Self)> {
+                inclusive_bounds!(range, $ty)
+            }
+
+            fn span(low: Self, high: Self) -> u128 {
+                high.wrapping_sub(low) as $unsigned as u128
+            }
+
+            fn offset(low: Self, offset: u128) -> Self {
+                low.wrapping_add(offset as $unsigned as $ty)
+            }
+        }
+    )*};
+}
+
+impl_sample_uniform!(
+    u8 => u8,
+    u16 => u16,
+    u32 => u32,
+    u64 => u64,
+    u128 => u128,
+    usize => usize,
+    i8 => u8,
+    i16 => u16,
+    i32 => u32,
+    i64 => u64,
+    i128 => u128,
+    isize => usize,
+);
+
+// Chars are sampled by their index among the Unicode scalar values, which
+// closes up the surrogate gap so every valid char is equally likely
+const SURROGATE_START: u32 = 0xD800;
+const SURROGATE_LEN: u32 = 0xE000 - 0xD800;
+
+fn char_index(c: char) -> u32 {
+    let value = c as u32;
+    if value >= SURROGATE_START {
+        value - SURROGATE_LEN
+    } else {
+        value
+    }
+}
+
+fn char_at(index: u32) -> char {
+    let value = if index >= SURROGATE_START {
+        index + SURROGATE_LEN
+    } else {
+        index
+    };
+    char::from_u32(value).unwrap()
+}
+
+impl SampleUniform for char {
+    fn inclusive_bounds<R: RangeBounds<Self>>(range: R) -> Option<(Self, Self)> {
+        use core::ops::Bound;
+        let last = char_index(char::MAX);
+        let low = match range.start_bound() {
+            Bound::Included(&low) => Some(char_index(low)),
+            Bound::Excluded(&low) => Some(char_index(low) + 1).filter(|&index| index <= last),
+            Bound::Unbounded => Some(0),
+        };
+        let high = match range.end_bound() {
+            Bound::Included(&high) => Some(char_index(high)),
+            Bound::Excluded(&high) => char_index(high).checked_sub(1),
+            Bound::Unbounded => Some(last),
+        };
+        match (low, high) {
+            (Some(low), Some(high)) if low <= high => Some((char_at(low), char_at(high))),
+            _ => None,
+        }
+    }
+
+    fn span(low: Self, high: Self) -> u128 {
+        (char_index(high) - char_index(low)) as u128
+    }
+
+    fn offset(low: Self, offset: u128) -> Self {
+        char_at(char_index(low) + offset as u32)
+    }
+}
+
+// Durations are sampled to the nanosecond
+impl SampleUniform for Duration {
+    fn inclusive_bounds<R: RangeBounds<Self>>(range: R) -> Option<(Self, Self)> {
+        use core::ops::Bound;
+        const NANOSECOND: Duration = Duration::from_nanos(1);
+        let low = match range.start_bound() {
+            Bound::Included(&low) => Some(low),
+            Bound::Excluded(&low) => low.checked_add(NANOSECOND),
+            Bound::Unbounded => Some(Duration::ZERO),
+        };
+        let high = match range.end_bound() {
+            Bound::Included(&high) => Some(high),
+            Bound::Excluded(&high) => high.checked_sub(NANOSECOND),
+            Bound::Unbounded => Some(Duration::MAX),
+        };
+        match (low, high) {
+            (Some(low), Some(high)) if low <= high => Some((low, high)),
+            _ => None,
+        }
+    }
+
+    fn span(low: Self, high: Self) -> u128 {
+        high.as_nanos() - low.as_nanos()
+    }
+
+    fn offset(low: Self, offset: u128) -> Self {
+        let nanos = low.as_nanos() + offset;
+        Duration::new(
+            (nanos / 1_000_000_000) as u64,
+            (nanos % 1_000_000_000) as u32,
+        )
+    }
+}
+
+/// A float type that [`RNG::gen_float_range`] can sample
+///
+/// Implemented for `f64` and `f32`, and usable as a bound in place of a
+/// `num-traits` `Float`:
+///
+/// ```rust
+/// use simple_rng::{RNG, SampleFloat};
+///
+/// fn noise<T: SampleFloat>(rng: &mut RNG, amplitude: T, zero: T) -> T {
+///     rng.gen_float_range(zero, amplitude)
+/// }
+///
+/// let mut rng = RNG::new(42);
+/// let single: f32 = noise(&mut rng, 0.5, 0.0);
+/// let double: f64 = noise(&mut rng, 0.5, 0.0);
+/// println!("{} {}", single, double);
+/// ```
+pub trait SampleFloat: Copy + PartialOrd {
+    /// Value in [min, max), or an error for non-finite or empty bounds
+    fn sample_range<A: RngAlgorithm>(
+        rng: &mut RNG<A>,
+        min: Self,
+        max: Self,
+    ) -> Result<Self, RngError>;
+}
+
+// Draw a unit value at the type's precision and scale it, halving both bounds
+// when the width overflows
+macro_rules! impl_sample_float {
+    ($($ty:ty => $unit:ident),* $(,)?) => {$(
+        impl SampleFloat for $ty {
+            fn sample_range<A: RngAlgorithm>(
+                rng: &mut RNG<A>,
+                min: Self,
+                max: Self,
+            ) -> Result<Self, RngError> {
+                if !(min.is_finite() && max.is_finite()) {
+                    return Err(RngError::NonFiniteBound);
+                }
+                if max <= min {
+                    return Err(RngError::EmptyRange);
+                }
+                let scale = max - min;
+                loop {
+                    let unit = rng.$unit();
+                    let value = if scale.is_finite() {
+                        min + scale * unit
+                    } else {
+                        2.0 * (min / 2.0 + (max / 2.0 - min / 2.0) * unit)
+                    };
+                    if value < max {
+                        return Ok(value);
+                    }
+                }
+            }
+        }
+    )*};
+}
+
+impl_sample_float!(f64 => gen_float, f32 => gen_f32);
+
+/// A range sampler that computes its rejection threshold once
+///
+/// [`RNG::gen_range`] works the threshold out with a division whenever a draw
+/// lands in the rejection zone. `Uniform` does that division up front in
+/// [`Uniform::new`], so each [`Uniform::sample`] is a multiply and a compare,
+/// which pays off when one range is sampled many times. Both give the same
+/// values from the same generator.
+///
+/// # Example
+/// ```rust
+/// use simple_rng::{RNG, Uniform};
+/// let mut rng = RNG::new(42);
+/// let die = Uniform::new(1, 6);
+/// let rolls: Vec<u32> = (0..10).map(|_| die.sample(&mut rng)).collect();
+/// println!("{:?}", rolls);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Uniform<T> {
+    low: T,
+    span: u128,
+    threshold: u64,
+}
+
+impl<T: SampleUniform> Uniform<T> {
+    /// Create a sampler for the range [min, max] (inclusive)
+    pub fn new(min: T, max: T) -> Self {
+        if max < min {
+            panic!("cannot sample empty range")
+        }
+        let span = T::span(min, max);
+        let threshold = if span < u64::MAX as u128 {
+            let bound = span as u64 + 1;
+            bound.wrapping_neg() % bound
+        } else {
+            0
+        };
+        Self {
+            low: min,
+            span,
+            threshold,
+        }
+    }
+
+    /// Draw a value from the range
+    pub fn sample<A: RngAlgorithm>(&self, rng: &mut RNG<A>) -> T {
+        if self.span >= u64::MAX as u128 {
+            return T::offset(self.low, rng.offset_in(self.span));
+        }
+        let bound = self.span as u64 + 1;
+        loop {
+            let product = rng.next() as u128 * bound as u128;
+            if product as u64 >= self.threshold {
+                return T::offset(self.low, product >> 64);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Uniform;
+    use crate::{RNG, RngAlgorithm, RngError};
+
+    struct Constant(u64);
+
+    impl RngAlgorithm for Constant {
+        fn next_u64(&mut self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    /// Every integer type stays within its range
+    fn test_all_types_in_range() {
+        let mut rng = RNG::new(17);
+        for _ in 0..1000 {
+            assert!((3..=9).contains(&rng.gen_range(3u8..=9)));
+            assert!((300..1000).contains(&rng.gen_range(300u16..1000)));
+            assert!((0..7).contains(&rng.gen_range(0usize..7)));
+            assert!((-5..=5).contains(&rng.gen_range(-5i32..=5)));
+            assert!((-100..-50).contains(&rng.gen_range(-100i16..-50)));
+            assert!((1 << 100..1 << 101).contains(&rng.gen_range(1u128 << 100..1 << 101)));
+            assert!((-3..=3).contains(&rng.gen_range(-3i128..=3)));
+        }
+    }
+
+    #[test]
+    /// Full-domain ranges of every width are accepted
+    fn test_full_domains() {
+        let mut rng = RNG::new(5);
+        rng.gen_range(..=u8::MAX);
+        rng.gen_range(i8::MIN..=i8::MAX);
+        rng.gen_range::<u128, _>(..);
+        rng.gen_range::<i128, _>(..);
+        rng.gen_range::<isize, _>(..);
+    }
+
+    #[test]
+    /// Narrow types hit every value of a small range
+    fn test_small_range_coverage() {
+        let mut rng = RNG::new(2);
+        let mut seen = [false; 4];
+        for _ in 0..200 {
+            seen[(rng.gen_range(-2i8..2) + 2) as usize] = true;
+        }
+        assert!(seen.iter().all(|&hit| hit));
+    }
+
+    #[test]
+    /// Signed ranges reach both ends, including `i64::MIN`
+    fn test_signed_ends() {
+        // An output of 1 maps to the bottom of any range narrower than 2^64,
+        // and u64::MAX to the top
+        let mut lowest = RNG::from_generator(Constant(1));
+        let mut highest = RNG::from_generator(Constant(u64::MAX));
+        assert_eq!(lowest.gen_range(-50i64..=50), -50);
+        assert_eq!(highest.gen_range(-50i64..=50), 50);
+        assert_eq!(lowest.gen_range(i64::MIN..0), i64::MIN);
+        assert_eq!(highest.gen_range(i64::MIN..0), -1);
+        assert_eq!(highest.gen_range(i64::MIN..=i64::MIN), i64::MIN);
+
+        // The full domain uses the output as the offset from i64::MIN
+        let mut zero = RNG::from_generator(Constant(0));
+        assert_eq!(zero.gen_range(i64::MIN..=i64::MAX), i64::MIN);
+        assert_eq!(highest.gen_range(i64::MIN..=i64::MAX), i64::MAX);
+    }
+
+    #[test]
+    /// A Uniform sampler gives the same values as gen_range
+    fn test_uniform_matches_gen_range() {
+        let mut sampled = RNG::new(9);
+        let mut ranged = RNG::new(9);
+        let die = Uniform::new(1u32, 6);
+        let wide = Uniform::new(i64::MIN, i64::MAX);
+        let huge = Uniform::new(0u128, u128::MAX / 3);
+        for _ in 0..1000 {
+            assert_eq!(die.sample(&mut sampled), ranged.gen_range(1u32..=6));
+            assert_eq!(
+                wide.sample(&mut sampled),
+                ranged.gen_range(i64::MIN..=i64::MAX)
+            );
+            assert_eq!(
+                huge.sample(&mut sampled),
+                ranged.gen_range(0..=u128::MAX / 3)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot sample empty range")]
+    /// A Uniform sampler can't be built for an empty range
+    fn test_uniform_empty() {
+        Uniform::new(6, 1);
+    }
+
+    #[test]
+    /// Char ranges skip the surrogate gap and honour excluded ends
+    fn test_char_ranges() {
+        let mut rng = RNG::new(13);
+        for _ in 0..1000 {
+            assert!(rng.gen_range('a'..='z').is_ascii_lowercase());
+            let c = rng.gen_range('\u{D7FF}'..='\u{E000}');
+            assert!(c == '\u{D7FF}' || c == '\u{E000}');
+        }
+        assert_eq!(rng.gen_range('\u{D7FF}'..'\u{E000}'), '\u{D7FF}');
+        assert_eq!(rng.gen_range(char::MAX..), char::MAX);
+    }
+
+    #[test]
+    /// f32 ranges stay in bounds, including the widest finite range
+    fn test_f32_ranges() {
+        let mut rng = RNG::new(21);
+        for _ in 0..1000 {
+            let value: f32 = rng.gen_float_range(-1.0, 1.0);
+            assert!((-1.0..1.0).contains(&value));
+            let value = rng.gen_float_range(-f32::MAX, f32::MAX);
+            assert!(value.is_finite());
+        }
+        assert_eq!(
+            rng.try_gen_float_range(2.0f32, 2.0),
+            Err(RngError::EmptyRange)
+        );
+        assert_eq!(
+            rng.try_gen_float_range(0.0, f32::NAN),
+            Err(RngError::NonFiniteBound)
+        );
+    }
+
+    #[test]
+    /// Duration ranges stay in bounds down to the nanosecond
+    fn test_duration_ranges() {
+        use core::time::Duration;
+        let mut rng = RNG::new(20);
+        let range = Duration::from_millis(100)..Duration::from_secs(2);
+        for _ in 0..1000 {
+            assert!(range.contains(&rng.gen_range(range.clone())));
+        }
+        let second = Duration::from_secs(1);
+        assert_eq!(rng.gen_range(second..=second), second);
+        assert_eq!(
+            rng.gen_range(second..second + Duration::from_nanos(1)),
+            second
+        );
+        rng.gen_range::<Duration, _>(..);
+    }
+}
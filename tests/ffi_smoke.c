@@ -0,0 +1,22 @@
+/* Smoke test for the C interface, run with `make ffi-check` */
+
+#include <assert.h>
+#include <stdio.h>
+
+#include "simple_rng.h"
+
+int main(void) {
+    SimpleRng *first = simplerng_new(42);
+    SimpleRng *second = simplerng_new(42);
+    assert(simplerng_next(first) == simplerng_next(second));
+    for (int i = 0; i < 1000; i++) {
+        uint64_t roll = simplerng_range(first, 1, 6);
+        assert(roll >= 1 && roll <= 6);
+    }
+    assert(simplerng_range(first, 9, 3) == 9);
+    simplerng_free(first);
+    simplerng_free(second);
+    simplerng_free(NULL);
+    puts("ffi ok");
+    return 0;
+}